@@ -0,0 +1,6 @@
+#! /usr/bin/env bash
+set -e
+msrv=$(grep '^rust-version' Cargo.toml | cut -d'"' -f2)
+# Resolve the newest dependencies compatible with the MSRV
+CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS=fallback cargo +stable generate-lockfile
+cargo "+${msrv}" check --all-features
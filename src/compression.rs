@@ -0,0 +1,290 @@
+//! Readers and writers that transparently handle the [`Compression`] of a stream of
+//! records.
+//!
+//! Zstandard support requires the `zstd` feature.
+use std::{
+    io::{self, Read},
+    ops::RangeInclusive,
+};
+
+#[cfg(not(feature = "zstd"))]
+use crate::Error;
+use crate::{enums::Compression, Result};
+
+/// The magic number at the start of every Zstandard frame.
+pub const ZSTD_MAGIC: u32 = 0xFD2FB528;
+/// The range of magic numbers of Zstandard skippable frames, such as the one
+/// containing DBZ [`Metadata`](crate::Metadata).
+pub const ZSTD_SKIPPABLE_MAGIC_RANGE: RangeInclusive<u32> = 0x184D2A50..=0x184D2A5F;
+
+/// A reader that decompresses the inner reader according to a [`Compression`]
+/// determined at runtime.
+pub struct DynReader<R: io::BufRead>(DynReaderImpl<R>);
+
+enum DynReaderImpl<R: io::BufRead> {
+    Uncompressed(Peeked<R>),
+    #[cfg(feature = "zstd")]
+    ZStd(zstd::stream::Decoder<'static, Peeked<R>>),
+}
+
+/// A reader with the bytes read while inferring its compression chained back in front
+/// of it.
+type Peeked<R> = io::Chain<io::Cursor<Vec<u8>>, R>;
+
+impl<R: io::BufRead> DynReader<R> {
+    /// Creates a new reader that decompresses `reader` according to `compression`.
+    ///
+    /// # Errors
+    /// This function returns an error if `compression` isn't supported with the
+    /// enabled features or the decompressor fails to initialize.
+    pub fn new(reader: R, compression: Compression) -> Result<Self> {
+        Self::with_peeked(Vec::new(), reader, compression)
+    }
+
+    /// Creates a new reader that infers the compression of `reader` from its first
+    /// bytes. Streams beginning with a Zstandard frame or skippable frame are treated
+    /// as Zstandard-compressed and all others as uncompressed.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from `reader` fails, or the stream is
+    /// Zstandard-compressed and the `zstd` feature isn't enabled.
+    pub fn inferred(mut reader: R) -> Result<Self> {
+        // A single `fill_buf` may return fewer bytes than the magic number, so read
+        // until there are enough bytes or the stream ends
+        let mut peeked = Vec::with_capacity(4);
+        (&mut reader).take(4).read_to_end(&mut peeked)?;
+        let compression = infer_compression(&peeked);
+        Self::with_peeked(peeked, reader, compression)
+    }
+
+    fn with_peeked(peeked: Vec<u8>, reader: R, compression: Compression) -> Result<Self> {
+        let reader = io::Cursor::new(peeked).chain(reader);
+        match compression {
+            Compression::None => Ok(Self(DynReaderImpl::Uncompressed(reader))),
+            #[cfg(feature = "zstd")]
+            Compression::ZStd => Ok(Self(DynReaderImpl::ZStd(
+                zstd::stream::Decoder::with_buffer(reader)?,
+            ))),
+            #[cfg(not(feature = "zstd"))]
+            Compression::ZStd => Err(Error::Decode(
+                "Decompressing Zstandard requires the `zstd` feature".to_owned(),
+            )),
+        }
+    }
+
+    /// Returns the compression of the inner reader.
+    pub fn compression(&self) -> Compression {
+        match self.0 {
+            DynReaderImpl::Uncompressed(_) => Compression::None,
+            #[cfg(feature = "zstd")]
+            DynReaderImpl::ZStd(_) => Compression::ZStd,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        match &self.0 {
+            DynReaderImpl::Uncompressed(reader) => reader.get_ref().1,
+            #[cfg(feature = "zstd")]
+            DynReaderImpl::ZStd(decoder) => decoder.get_ref().get_ref().1,
+        }
+    }
+}
+
+impl<R: io::BufRead> io::Read for DynReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            DynReaderImpl::Uncompressed(reader) => reader.read(buf),
+            #[cfg(feature = "zstd")]
+            DynReaderImpl::ZStd(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+/// A writer that compresses its output according to a [`Compression`] determined at
+/// runtime.
+///
+/// [`DynWriter::finish`] must be called once writing is complete to ensure any
+/// compressed output is written.
+pub struct DynWriter<W: io::Write>(DynWriterImpl<W>);
+
+enum DynWriterImpl<W: io::Write> {
+    Uncompressed(W),
+    #[cfg(feature = "zstd")]
+    ZStd(zstd::stream::Encoder<'static, W>),
+}
+
+impl<W: io::Write> DynWriter<W> {
+    /// Creates a new writer that compresses output to `writer` according to
+    /// `compression`.
+    ///
+    /// # Errors
+    /// This function returns an error if `compression` isn't supported with the
+    /// enabled features or the compressor fails to initialize.
+    pub fn new(writer: W, compression: Compression) -> Result<Self> {
+        match compression {
+            Compression::None => Ok(Self(DynWriterImpl::Uncompressed(writer))),
+            #[cfg(feature = "zstd")]
+            Compression::ZStd => Ok(Self(DynWriterImpl::ZStd(zstd::stream::Encoder::new(
+                writer,
+                zstd::DEFAULT_COMPRESSION_LEVEL,
+            )?))),
+            #[cfg(not(feature = "zstd"))]
+            Compression::ZStd => Err(Error::Encode(
+                "Compressing Zstandard requires the `zstd` feature".to_owned(),
+            )),
+        }
+    }
+
+    /// Returns the compression of the output.
+    pub fn compression(&self) -> Compression {
+        match self.0 {
+            DynWriterImpl::Uncompressed(_) => Compression::None,
+            #[cfg(feature = "zstd")]
+            DynWriterImpl::ZStd(_) => Compression::ZStd,
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        match &self.0 {
+            DynWriterImpl::Uncompressed(writer) => writer,
+            #[cfg(feature = "zstd")]
+            DynWriterImpl::ZStd(encoder) => encoder.get_ref(),
+        }
+    }
+
+    /// Completes any compressed output and returns the inner writer.
+    ///
+    /// # Errors
+    /// This function returns an error if writing the remaining output fails.
+    pub fn finish(self) -> Result<W> {
+        match self.0 {
+            DynWriterImpl::Uncompressed(mut writer) => {
+                writer.flush()?;
+                Ok(writer)
+            }
+            #[cfg(feature = "zstd")]
+            DynWriterImpl::ZStd(encoder) => Ok(encoder.finish()?),
+        }
+    }
+}
+
+impl<W: io::Write> io::Write for DynWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            DynWriterImpl::Uncompressed(writer) => writer.write(buf),
+            #[cfg(feature = "zstd")]
+            DynWriterImpl::ZStd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.0 {
+            DynWriterImpl::Uncompressed(writer) => writer.flush(),
+            #[cfg(feature = "zstd")]
+            DynWriterImpl::ZStd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Infers the compression of a stream from its first bytes.
+fn infer_compression(bytes: &[u8]) -> Compression {
+    match bytes.get(..4) {
+        Some(magic) => {
+            let magic = u32::from_le_bytes(magic.try_into().unwrap());
+            if magic == ZSTD_MAGIC || ZSTD_SKIPPABLE_MAGIC_RANGE.contains(&magic) {
+                Compression::ZStd
+            } else {
+                Compression::None
+            }
+        }
+        None => Compression::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+    use crate::test_utils::TrickleReader;
+
+    #[test]
+    fn test_infer_compression() {
+        assert_eq!(
+            infer_compression(&ZSTD_MAGIC.to_le_bytes()),
+            Compression::ZStd
+        );
+        assert_eq!(
+            infer_compression(&crate::metadata::METADATA_MAGIC.to_le_bytes()),
+            Compression::ZStd
+        );
+        assert_eq!(infer_compression(&[14, 0xA0, 1, 0]), Compression::None);
+        assert_eq!(infer_compression(&[0x28, 0xB5]), Compression::None);
+    }
+
+    #[test]
+    fn test_uncompressed_round_trip() {
+        let mut writer = DynWriter::new(Vec::new(), Compression::None).unwrap();
+        writer.write_all(b"records").unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_eq!(bytes, b"records");
+        let mut reader = DynReader::inferred(bytes.as_slice()).unwrap();
+        assert_eq!(reader.compression(), Compression::None);
+        let mut res = Vec::new();
+        reader.read_to_end(&mut res).unwrap();
+        assert_eq!(res, b"records");
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_round_trip() {
+        let mut writer = DynWriter::new(Vec::new(), Compression::ZStd).unwrap();
+        writer.write_all(b"records").unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_ne!(bytes, b"records");
+        let mut reader = DynReader::inferred(bytes.as_slice()).unwrap();
+        assert_eq!(reader.compression(), Compression::ZStd);
+        let mut res = Vec::new();
+        reader.read_to_end(&mut res).unwrap();
+        assert_eq!(res, b"records");
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_inferred_partial_reads() {
+        let mut writer = DynWriter::new(Vec::new(), Compression::ZStd).unwrap();
+        writer.write_all(b"records").unwrap();
+        let bytes = writer.finish().unwrap();
+        let mut reader = DynReader::inferred(TrickleReader(&bytes)).unwrap();
+        assert_eq!(reader.compression(), Compression::ZStd);
+        let mut res = Vec::new();
+        reader.read_to_end(&mut res).unwrap();
+        assert_eq!(res, b"records");
+    }
+
+    #[test]
+    fn test_inferred_partial_reads() {
+        let mut reader = DynReader::inferred(TrickleReader(b"records")).unwrap();
+        assert_eq!(reader.compression(), Compression::None);
+        let mut res = Vec::new();
+        reader.read_to_end(&mut res).unwrap();
+        assert_eq!(res, b"records");
+        let reader = DynReader::inferred(TrickleReader(b"re")).unwrap();
+        assert_eq!(reader.compression(), Compression::None);
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_zstd_unsupported() {
+        assert!(matches!(
+            DynReader::new([].as_slice(), Compression::ZStd),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            DynWriter::new(Vec::new(), Compression::ZStd),
+            Err(Error::Encode(_))
+        ));
+    }
+}
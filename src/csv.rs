@@ -0,0 +1,529 @@
+//! Encoding of records as comma-separated values per
+//! [`Encoding::Csv`](crate::enums::Encoding::Csv).
+use std::{fmt::Display, io, os::raw::c_char};
+
+use crate::{
+    record::{
+        BidAskPair, GatewayErrorMsg, Imbalance, InstrumentDefMsg, MboMsg, Mbp10Msg, Mbp1Msg,
+        OhlcvMsg, RecordHeader, StatMsg, StatusMsg, SymbolMappingMsg, TradeMsg,
+    },
+    FixedStr, RecordRefEnum, Result,
+};
+
+/// A record that can be serialized as a CSV row.
+pub trait CsvSerialize {
+    /// Writes the CSV header row naming each column of the record type.
+    ///
+    /// # Errors
+    /// This function returns an error if writing to `writer` fails.
+    fn serialize_header<W: io::Write>(writer: &mut W) -> io::Result<()>;
+
+    /// Writes the record as a CSV row.
+    ///
+    /// # Errors
+    /// This function returns an error if writing to `writer` fails.
+    fn serialize_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+/// Encodes records as CSV with one row per record.
+pub struct CsvEncoder<W> {
+    writer: W,
+}
+
+impl<W: io::Write> CsvEncoder<W> {
+    /// Creates a new encoder that writes CSV to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the encoder and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Encodes the header row for records of type `T`.
+    ///
+    /// # Errors
+    /// This function returns an error if writing to the underlying writer fails.
+    pub fn encode_header<T: CsvSerialize>(&mut self) -> Result<()> {
+        T::serialize_header(&mut self.writer)?;
+        Ok(())
+    }
+
+    /// Encodes a single record as a row without a header.
+    ///
+    /// # Errors
+    /// This function returns an error if writing to the underlying writer fails.
+    pub fn encode_record<T: CsvSerialize>(&mut self, record: &T) -> Result<()> {
+        record.serialize_to(&mut self.writer)?;
+        Ok(())
+    }
+
+    /// Encodes a header row followed by one row for each record.
+    ///
+    /// # Errors
+    /// This function returns an error if writing to the underlying writer fails.
+    pub fn encode_records<T: CsvSerialize>(&mut self, records: &[T]) -> Result<()> {
+        self.encode_header::<T>()?;
+        records.iter().try_for_each(|rec| self.encode_record(rec))
+    }
+
+    /// Encodes a single record of any type as a row without a header.
+    ///
+    /// # Errors
+    /// This function returns an error if writing to the underlying writer fails.
+    pub fn encode_ref_enum(&mut self, record: RecordRefEnum) -> Result<()> {
+        match record {
+            RecordRefEnum::Mbo(rec) => self.encode_record(rec),
+            RecordRefEnum::Trade(rec) => self.encode_record(rec),
+            RecordRefEnum::Mbp1(rec) => self.encode_record(rec),
+            RecordRefEnum::Mbp10(rec) => self.encode_record(rec),
+            RecordRefEnum::Ohlcv(rec) => self.encode_record(rec),
+            RecordRefEnum::Status(rec) => self.encode_record(rec),
+            RecordRefEnum::InstrumentDef(rec) => self.encode_record(rec),
+            RecordRefEnum::Imbalance(rec) => self.encode_record(rec),
+            RecordRefEnum::Error(rec) => self.encode_record(rec),
+            RecordRefEnum::SymbolMapping(rec) => self.encode_record(rec),
+            RecordRefEnum::Stat(rec) => self.encode_record(rec),
+        }
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    /// This function returns an error if flushing the underlying writer fails.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes the fields of a single row, inserting separators between them.
+struct Row<'a, W> {
+    writer: &'a mut W,
+    is_first: bool,
+}
+
+impl<'a, W: io::Write> Row<'a, W> {
+    fn new(writer: &'a mut W) -> Self {
+        Self {
+            writer,
+            is_first: true,
+        }
+    }
+
+    fn value(&mut self, value: impl Display) -> io::Result<()> {
+        if self.is_first {
+            self.is_first = false;
+        } else {
+            self.writer.write_all(b",")?;
+        }
+        write!(self.writer, "{value}")
+    }
+
+    fn str(&mut self, s: &str) -> io::Result<()> {
+        if s.contains([',', '"', '\n', '\r']) {
+            self.value(format_args!("\"{}\"", s.replace('"', "\"\"")))
+        } else {
+            self.value(s)
+        }
+    }
+
+    fn c_char(&mut self, c: c_char) -> io::Result<()> {
+        if c == 0 {
+            self.value("")
+        } else {
+            self.str((c as u8 as char).encode_utf8(&mut [0; 4]))
+        }
+    }
+
+    fn end(self) -> io::Result<()> {
+        self.writer.write_all(b"\n")
+    }
+}
+
+/// A field that can be written as one or more CSV columns.
+trait CsvField {
+    fn write_header<W: io::Write>(name: &str, row: &mut Row<W>) -> io::Result<()> {
+        row.value(name)
+    }
+
+    fn write_field<W: io::Write>(&self, row: &mut Row<W>) -> io::Result<()>;
+}
+
+macro_rules! impl_csv_field_display {
+    ($($ty:ty),*) => {
+        $(
+            impl CsvField for $ty {
+                fn write_field<W: io::Write>(&self, row: &mut Row<W>) -> io::Result<()> {
+                    row.value(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_csv_field_display!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl CsvField for RecordHeader {
+    fn write_header<W: io::Write>(_name: &str, row: &mut Row<W>) -> io::Result<()> {
+        ["rtype", "publisher_id", "product_id", "ts_event"]
+            .into_iter()
+            .try_for_each(|name| row.value(name))
+    }
+
+    fn write_field<W: io::Write>(&self, row: &mut Row<W>) -> io::Result<()> {
+        row.value(self.rtype)?;
+        row.value(self.publisher_id)?;
+        row.value(self.product_id)?;
+        row.value(self.ts_event)
+    }
+}
+
+impl<const N: usize> CsvField for [BidAskPair; N] {
+    fn write_header<W: io::Write>(_name: &str, row: &mut Row<W>) -> io::Result<()> {
+        for i in 0..N {
+            for name in ["bid_px", "ask_px", "bid_sz", "ask_sz", "bid_ct", "ask_ct"] {
+                row.value(format_args!("{name}_{i:02}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_field<W: io::Write>(&self, row: &mut Row<W>) -> io::Result<()> {
+        for level in self {
+            row.value(level.bid_px)?;
+            row.value(level.ask_px)?;
+            row.value(level.bid_sz)?;
+            row.value(level.ask_sz)?;
+            row.value(level.bid_ct)?;
+            row.value(level.ask_ct)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> CsvField for FixedStr<N> {
+    fn write_field<W: io::Write>(&self, row: &mut Row<W>) -> io::Result<()> {
+        row.str(self.to_string_lossy().trim())
+    }
+}
+
+/// Writes the column names of a field, inferring the field's type from a projection so
+/// the header can be written without a record instance.
+fn write_header_of<R, F: CsvField, W: io::Write>(
+    _: fn(&R) -> &F,
+    name: &str,
+    row: &mut Row<W>,
+) -> io::Result<()> {
+    F::write_header(name, row)
+}
+
+macro_rules! csv_column {
+    (header, $row:ident, $ty:ty, $field:ident) => {
+        write_header_of(|rec: &$ty| &rec.$field, stringify!($field), &mut $row)
+    };
+    (header, $row:ident, $ty:ty, $field:ident, char) => {
+        $row.value(stringify!($field))
+    };
+    (value, $row:ident, $rec:ident, $field:ident) => {
+        $rec.$field.write_field(&mut $row)
+    };
+    (value, $row:ident, $rec:ident, $field:ident, char) => {
+        $row.c_char($rec.$field)
+    };
+}
+
+/// Implements [`CsvSerialize`] for a record from its fields in column order. Fields
+/// marked `: char` are `c_char`s written as characters.
+macro_rules! impl_csv_serialize {
+    ($ty:ty { $($field:ident $(: $kind:ident)?),* $(,)? }) => {
+        impl CsvSerialize for $ty {
+            fn serialize_header<W: io::Write>(writer: &mut W) -> io::Result<()> {
+                let mut row = Row::new(writer);
+                $(csv_column!(header, row, $ty, $field $(, $kind)?)?;)*
+                row.end()
+            }
+
+            fn serialize_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+                let mut row = Row::new(writer);
+                $(csv_column!(value, row, self, $field $(, $kind)?)?;)*
+                row.end()
+            }
+        }
+    };
+}
+
+impl_csv_serialize!(MboMsg {
+    hd,
+    order_id,
+    price,
+    size,
+    flags,
+    channel_id,
+    action: char,
+    side: char,
+    ts_recv,
+    ts_in_delta,
+    sequence,
+});
+
+impl_csv_serialize!(TradeMsg {
+    hd,
+    price,
+    size,
+    action: char,
+    side: char,
+    flags,
+    depth,
+    ts_recv,
+    ts_in_delta,
+    sequence,
+});
+
+impl_csv_serialize!(Mbp1Msg {
+    hd,
+    price,
+    size,
+    action: char,
+    side: char,
+    flags,
+    depth,
+    ts_recv,
+    ts_in_delta,
+    sequence,
+    booklevel,
+});
+
+impl_csv_serialize!(Mbp10Msg {
+    hd,
+    price,
+    size,
+    action: char,
+    side: char,
+    flags,
+    depth,
+    ts_recv,
+    ts_in_delta,
+    sequence,
+    booklevel,
+});
+
+impl_csv_serialize!(OhlcvMsg {
+    hd,
+    open,
+    high,
+    low,
+    close,
+    volume,
+});
+
+impl_csv_serialize!(StatusMsg {
+    hd,
+    ts_recv,
+    group,
+    trading_status,
+    halt_reason,
+    trading_event,
+});
+
+impl_csv_serialize!(InstrumentDefMsg {
+    hd,
+    ts_recv,
+    min_price_increment,
+    display_factor,
+    expiration,
+    activation,
+    high_limit_price,
+    low_limit_price,
+    max_price_variation,
+    trading_reference_price,
+    unit_of_measure_qty,
+    min_price_increment_amount,
+    price_ratio,
+    inst_attrib_value,
+    underlying_id,
+    cleared_volume,
+    market_depth_implied,
+    market_depth,
+    market_segment_id,
+    max_trade_vol,
+    min_lot_size,
+    min_lot_size_block,
+    min_lot_size_round_lot,
+    min_trade_vol,
+    open_interest_qty,
+    contract_multiplier,
+    decay_quantity,
+    original_contract_size,
+    related_security_id,
+    trading_reference_date,
+    appl_id,
+    maturity_year,
+    decay_start_date,
+    channel_id,
+    currency,
+    settl_currency,
+    secsubtype,
+    symbol,
+    group,
+    exchange,
+    asset,
+    cfi,
+    security_type,
+    unit_of_measure,
+    underlying,
+    related,
+    match_algorithm: char,
+    md_security_trading_status,
+    main_fraction,
+    price_display_format,
+    settl_price_type,
+    sub_fraction,
+    underlying_product,
+    security_update_action: char,
+    maturity_month,
+    maturity_day,
+    maturity_week,
+    user_defined_instrument: char,
+    contract_multiplier_unit,
+    flow_schedule_type,
+    tick_rule,
+});
+
+impl_csv_serialize!(Imbalance {
+    hd,
+    ts_recv,
+    ref_price,
+    auction_time,
+    cont_book_clr_price,
+    auct_interest_clr_price,
+    ssr_filling_price,
+    ind_match_price,
+    upper_collar,
+    lower_collar,
+    paired_qty,
+    total_imbalance_qty,
+    market_imbalance_qty,
+    auction_type: char,
+    side: char,
+    auction_status,
+    freeze_status,
+    num_extensions,
+    unpaired_qty,
+    unpaired_side: char,
+    significant_imbalance: char,
+});
+
+impl_csv_serialize!(GatewayErrorMsg { hd, err });
+
+impl_csv_serialize!(SymbolMappingMsg {
+    hd,
+    stype_in_symbol,
+    stype_out_symbol,
+    start_ts,
+    end_ts,
+});
+
+impl_csv_serialize!(StatMsg {
+    hd,
+    ts_recv,
+    ts_ref,
+    price,
+    quantity,
+    sequence,
+    ts_in_delta,
+    stat_type,
+    channel_id,
+    update_action,
+    stat_flags,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::OHLCV_MSG;
+
+    fn encode_records<T: CsvSerialize>(records: &[T]) -> String {
+        let mut encoder = CsvEncoder::new(Vec::new());
+        encoder.encode_records(records).unwrap();
+        String::from_utf8(encoder.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_ohlcv() {
+        assert_eq!(
+            encode_records(&[OHLCV_MSG]),
+            "rtype,publisher_id,product_id,ts_event,open,high,low,close,volume\n\
+             17,1,5482,1609160400000000000,372025000000000,372050000000000,372025000000000,372050000000000,57\n"
+        );
+    }
+
+    #[test]
+    fn test_mbp10_header() {
+        let mut header = Vec::new();
+        Mbp10Msg::serialize_header(&mut header).unwrap();
+        let header = String::from_utf8(header).unwrap();
+        let columns: Vec<&str> = header.trim_end().split(',').collect();
+        assert_eq!(columns.len(), 13 + 10 * 6);
+        assert_eq!(columns[13], "bid_px_00");
+        assert_eq!(columns[14], "ask_px_00");
+        assert_eq!(*columns.last().unwrap(), "ask_ct_09");
+    }
+
+    #[test]
+    fn test_mbp1_and_c_chars() {
+        let mbp1 = Mbp1Msg {
+            hd: RecordHeader::new::<Mbp1Msg>(1, 323, 1658441851000000000),
+            price: 3720250000000,
+            size: 1,
+            action: 'A' as c_char,
+            side: 'B' as c_char,
+            flags: 128,
+            depth: 0,
+            ts_recv: 1658441891000000000,
+            ts_in_delta: 22993,
+            sequence: 1170352,
+            booklevel: [BidAskPair {
+                bid_px: 3720250000000,
+                ask_px: 3720500000000,
+                bid_sz: 24,
+                ask_sz: 11,
+                bid_ct: 15,
+                ask_ct: 9,
+            }],
+        };
+        let mut row = Vec::new();
+        mbp1.serialize_to(&mut row).unwrap();
+        assert_eq!(
+            String::from_utf8(row).unwrap(),
+            "1,1,323,1658441851000000000,3720250000000,1,A,B,128,0,1658441891000000000,22993,1170352,3720250000000,3720500000000,24,11,15,9\n"
+        );
+        let error = GatewayErrorMsg {
+            hd: RecordHeader::new::<GatewayErrorMsg>(0, 0, 0),
+            err: FixedStr::try_from("Bad request, \"retry\"  ").unwrap(),
+        };
+        assert_eq!(
+            encode_records(&[error]),
+            "rtype,publisher_id,product_id,ts_event,err\n21,0,0,0,\"Bad request, \"\"retry\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_c_char_quoting() {
+        let imbalance = Imbalance {
+            significant_imbalance: ',' as c_char,
+            unpaired_side: '"' as c_char,
+            ..Imbalance::default()
+        };
+        let mut row = Vec::new();
+        imbalance.serialize_to(&mut row).unwrap();
+        let row = String::from_utf8(row).unwrap();
+        assert!(row.ends_with(",\"\"\"\",\",\"\n"), "{row}");
+    }
+}
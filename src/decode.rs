@@ -0,0 +1,236 @@
+//! Decoding of streams of records.
+use std::{io, mem, slice};
+
+use crate::{
+    compression::DynReader,
+    enums::Compression,
+    record::{ConstTypeId, RecordHeader},
+    Error, RecordEnum, RecordRef, RecordRefEnum, Result,
+};
+
+/// The maximum size of a record in bytes, as limited by [`RecordHeader::length`].
+const MAX_RECORD_SIZE: usize = u8::MAX as usize * 4;
+
+/// Decodes records from a stream of bytes in the binary layout of the types in
+/// [`record`](crate::record).
+///
+/// A single internal buffer is reused between records, so each decoded record
+/// borrows from the decoder and is valid until the next call to a `decode_*` method.
+pub struct RecordDecoder<R> {
+    reader: R,
+    // Backed by `u64` to guarantee the alignment required by `RecordRef`.
+    buffer: Vec<u64>,
+}
+
+impl<R: io::Read> RecordDecoder<R> {
+    /// Creates a new decoder that reads records from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: vec![0; MAX_RECORD_SIZE / mem::size_of::<u64>() + 1],
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes the decoder and returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Decodes the next record, returning `None` once the end of the stream is
+    /// reached.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from the underlying reader fails or
+    /// the stream ends partway through a record.
+    pub fn decode_ref(&mut self) -> Result<Option<RecordRef<'_>>> {
+        const HEADER_SIZE: usize = mem::size_of::<RecordHeader>();
+
+        let Self { reader, buffer } = self;
+        // Safety: `buffer` is large enough to hold `MAX_RECORD_SIZE` bytes and any bit
+        // pattern is a valid `u8`.
+        let bytes =
+            unsafe { slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, MAX_RECORD_SIZE) };
+        match read_to_fill(reader, &mut bytes[..HEADER_SIZE])? {
+            0 => return Ok(None),
+            HEADER_SIZE => (),
+            read => {
+                return Err(Error::Decode(format!(
+                    "Unexpected end of stream after {read} bytes of a record header"
+                )))
+            }
+        }
+        let record_size = bytes[0] as usize * 4;
+        if record_size < HEADER_SIZE {
+            return Err(Error::Decode(format!(
+                "Record length of {record_size} bytes is shorter than a record header"
+            )));
+        }
+        let read = read_to_fill(reader, &mut bytes[HEADER_SIZE..record_size])?;
+        if read < record_size - HEADER_SIZE {
+            return Err(Error::Decode(format!(
+                "Unexpected end of stream after {} of {record_size} bytes of a record",
+                HEADER_SIZE + read
+            )));
+        }
+        RecordRef::try_from_bytes(&bytes[..record_size]).map(Some)
+    }
+
+    /// Decodes the next record as a `T`, returning `None` once the end of the stream
+    /// is reached.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from the underlying reader fails, the
+    /// stream ends partway through a record, or the record isn't a `T`.
+    pub fn decode_record<T: ConstTypeId>(&mut self) -> Result<Option<&T>> {
+        self.decode_ref()?.map(|rec| rec.try_get()).transpose()
+    }
+
+    /// Decodes the next record as a [`RecordRefEnum`], returning `None` once the end of
+    /// the stream is reached.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from the underlying reader fails, the
+    /// stream ends partway through a record, or the record has an unknown `rtype`.
+    pub fn decode_ref_enum(&mut self) -> Result<Option<RecordRefEnum<'_>>> {
+        self.decode_ref()?.map(RecordRefEnum::try_from).transpose()
+    }
+
+    /// Decodes the next record as an owned [`RecordEnum`], returning `None` once the end
+    /// of the stream is reached.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from the underlying reader fails, the
+    /// stream ends partway through a record, or the record has an unknown `rtype`.
+    pub fn decode_record_enum(&mut self) -> Result<Option<RecordEnum>> {
+        Ok(self.decode_ref_enum()?.map(RecordEnum::from))
+    }
+
+    /// Decodes all remaining records in the stream as `T`s.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from the underlying reader fails, the
+    /// stream ends partway through a record, or any record isn't a `T`.
+    pub fn decode_records<T: ConstTypeId + Clone>(&mut self) -> Result<Vec<T>> {
+        let mut records = Vec::new();
+        while let Some(rec) = self.decode_record::<T>()? {
+            records.push(rec.clone());
+        }
+        Ok(records)
+    }
+}
+
+impl<R: io::BufRead> RecordDecoder<DynReader<R>> {
+    /// Creates a new decoder that reads records from `reader`, decompressing them
+    /// according to `compression`.
+    ///
+    /// # Errors
+    /// This function returns an error if `compression` isn't supported with the
+    /// enabled features.
+    pub fn with_compression(reader: R, compression: Compression) -> Result<Self> {
+        DynReader::new(reader, compression).map(Self::new)
+    }
+
+    /// Creates a new decoder that reads records from `reader`, inferring the
+    /// compression from the first bytes of the stream.
+    ///
+    /// # Errors
+    /// This function returns an error if reading from `reader` fails or the inferred
+    /// compression isn't supported with the enabled features.
+    pub fn inferred(reader: R) -> Result<Self> {
+        DynReader::inferred(reader).map(Self::new)
+    }
+}
+
+/// Reads from `reader` until `buf` is full or the end of the stream is reached,
+/// returning the number of bytes read.
+fn read_to_fill(reader: &mut impl io::Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        record::{MboMsg, OhlcvMsg},
+        test_utils::{TrickleReader, MBO_MSG, OHLCV_MSG},
+    };
+
+    fn mixed_stream() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(RecordRef::from(&OHLCV_MSG).as_bytes());
+        bytes.extend_from_slice(RecordRef::from(&MBO_MSG).as_bytes());
+        bytes.extend_from_slice(RecordRef::from(&OHLCV_MSG).as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_decode_ref_enum() {
+        let bytes = mixed_stream();
+        let mut decoder = RecordDecoder::new(TrickleReader(&bytes));
+        assert_eq!(
+            decoder.decode_ref_enum().unwrap(),
+            Some(RecordRefEnum::Ohlcv(&OHLCV_MSG))
+        );
+        assert_eq!(
+            decoder.decode_ref_enum().unwrap(),
+            Some(RecordRefEnum::Mbo(&MBO_MSG))
+        );
+        assert_eq!(
+            decoder.decode_record_enum().unwrap(),
+            Some(RecordEnum::Ohlcv(OHLCV_MSG))
+        );
+        assert!(decoder.decode_ref_enum().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decode_records() {
+        let mut bytes = Vec::new();
+        for _ in 0..3 {
+            bytes.extend_from_slice(RecordRef::from(&OHLCV_MSG).as_bytes());
+        }
+        let records = RecordDecoder::new(bytes.as_slice())
+            .decode_records::<OhlcvMsg>()
+            .unwrap();
+        assert_eq!(records, vec![OHLCV_MSG; 3]);
+    }
+
+    #[test]
+    fn test_decode_wrong_type() {
+        let bytes = mixed_stream();
+        let mut decoder = RecordDecoder::new(bytes.as_slice());
+        assert!(matches!(
+            decoder.decode_record::<MboMsg>(),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        let bytes = mixed_stream();
+        for len in [bytes.len() - 1, bytes.len() - 50] {
+            let mut decoder = RecordDecoder::new(&bytes[..len]);
+            decoder.decode_ref().unwrap().unwrap();
+            decoder.decode_ref().unwrap().unwrap();
+            assert!(matches!(decoder.decode_ref(), Err(Error::Decode(_))));
+        }
+    }
+}
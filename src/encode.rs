@@ -0,0 +1,193 @@
+//! Encoding of records into a stream of bytes.
+use std::{io, mem};
+
+use crate::{
+    compression::DynWriter, enums::Compression, record::ConstTypeId, Error, RecordEnum, RecordRef,
+    RecordRefEnum, Result,
+};
+
+/// Encodes records into a stream of bytes in the binary layout of the types in
+/// [`record`](crate::record), the inverse of [`RecordDecoder`](crate::RecordDecoder).
+pub struct RecordEncoder<W> {
+    writer: W,
+}
+
+impl<W: io::Write> RecordEncoder<W> {
+    /// Creates a new encoder that writes records to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the encoder and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Encodes a single record.
+    ///
+    /// # Errors
+    /// This function returns an error if the record's header doesn't match its type,
+    /// i.e. `hd.length` isn't the size of `T` in 32-bit words or `hd.rtype` isn't
+    /// `T::TYPE_ID`, or writing to the underlying writer fails.
+    pub fn encode_record<T: ConstTypeId>(&mut self, record: &T) -> Result<()> {
+        let rec_ref = RecordRef::from(record);
+        let header = rec_ref.header();
+        if header.length as usize * 4 != mem::size_of::<T>() {
+            return Err(Error::Encode(format!(
+                "Header length of {} words doesn't match the record size of {} bytes",
+                header.length,
+                mem::size_of::<T>()
+            )));
+        }
+        if header.rtype != T::TYPE_ID {
+            return Err(Error::Encode(format!(
+                "Header rtype {:#04X} doesn't match the expected rtype {:#04X}",
+                header.rtype,
+                T::TYPE_ID
+            )));
+        }
+        self.writer.write_all(rec_ref.as_bytes())?;
+        Ok(())
+    }
+
+    /// Encodes a slice of records.
+    ///
+    /// # Errors
+    /// This function returns an error if any record's header doesn't match its type or
+    /// writing to the underlying writer fails.
+    pub fn encode_records<T: ConstTypeId>(&mut self, records: &[T]) -> Result<()> {
+        records.iter().try_for_each(|rec| self.encode_record(rec))
+    }
+
+    /// Encodes a record of any type.
+    ///
+    /// # Errors
+    /// This function returns an error if the record's header doesn't match its type or
+    /// writing to the underlying writer fails.
+    pub fn encode_ref_enum(&mut self, record: RecordRefEnum) -> Result<()> {
+        match record {
+            RecordRefEnum::Mbo(rec) => self.encode_record(rec),
+            RecordRefEnum::Trade(rec) => self.encode_record(rec),
+            RecordRefEnum::Mbp1(rec) => self.encode_record(rec),
+            RecordRefEnum::Mbp10(rec) => self.encode_record(rec),
+            RecordRefEnum::Ohlcv(rec) => self.encode_record(rec),
+            RecordRefEnum::Status(rec) => self.encode_record(rec),
+            RecordRefEnum::InstrumentDef(rec) => self.encode_record(rec),
+            RecordRefEnum::Imbalance(rec) => self.encode_record(rec),
+            RecordRefEnum::Error(rec) => self.encode_record(rec),
+            RecordRefEnum::SymbolMapping(rec) => self.encode_record(rec),
+            RecordRefEnum::Stat(rec) => self.encode_record(rec),
+        }
+    }
+
+    /// Encodes an owned record of any type.
+    ///
+    /// # Errors
+    /// This function returns an error if the record's header doesn't match its type or
+    /// writing to the underlying writer fails.
+    pub fn encode_record_enum(&mut self, record: &RecordEnum) -> Result<()> {
+        self.encode_ref_enum(record.as_ref_enum())
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    /// This function returns an error if flushing the underlying writer fails.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: io::Write> RecordEncoder<DynWriter<W>> {
+    /// Creates a new encoder that writes records to `writer`, compressing them
+    /// according to `compression`. [`RecordEncoder::finish`] must be called once all
+    /// records have been encoded.
+    ///
+    /// # Errors
+    /// This function returns an error if `compression` isn't supported with the
+    /// enabled features.
+    pub fn with_compression(writer: W, compression: Compression) -> Result<Self> {
+        DynWriter::new(writer, compression).map(Self::new)
+    }
+
+    /// Completes any compressed output and returns the underlying writer.
+    ///
+    /// # Errors
+    /// This function returns an error if writing the remaining output fails.
+    pub fn finish(self) -> Result<W> {
+        self.writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        record::{OhlcvMsg, StatusMsg},
+        test_utils::OHLCV_MSG,
+        RecordDecoder,
+    };
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let mut encoder = RecordEncoder::new(Vec::new());
+        encoder.encode_records(&[OHLCV_MSG, OHLCV_MSG]).unwrap();
+        encoder
+            .encode_record_enum(&RecordEnum::Ohlcv(OHLCV_MSG))
+            .unwrap();
+        let bytes = encoder.into_inner();
+        assert_eq!(bytes.len(), 3 * mem::size_of::<OhlcvMsg>());
+        let records = RecordDecoder::new(bytes.as_slice())
+            .decode_records::<OhlcvMsg>()
+            .unwrap();
+        assert_eq!(records, vec![OHLCV_MSG; 3]);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_round_trip() {
+        let mut encoder = RecordEncoder::with_compression(Vec::new(), Compression::ZStd).unwrap();
+        encoder.encode_records(&[OHLCV_MSG, OHLCV_MSG]).unwrap();
+        let bytes = encoder.finish().unwrap();
+        let records = RecordDecoder::inferred(bytes.as_slice())
+            .unwrap()
+            .decode_records::<OhlcvMsg>()
+            .unwrap();
+        assert_eq!(records, vec![OHLCV_MSG; 2]);
+    }
+
+    #[test]
+    fn test_encode_invalid_length() {
+        let mut ohlcv = OHLCV_MSG;
+        ohlcv.hd.length = 56;
+        let mut encoder = RecordEncoder::new(Vec::new());
+        assert!(matches!(
+            encoder.encode_record(&ohlcv),
+            Err(Error::Encode(_))
+        ));
+        assert!(encoder.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_encode_invalid_rtype() {
+        let mut ohlcv = OHLCV_MSG;
+        ohlcv.hd.rtype = StatusMsg::TYPE_ID;
+        let mut encoder = RecordEncoder::new(Vec::new());
+        assert!(matches!(
+            encoder.encode_record(&ohlcv),
+            Err(Error::Encode(_))
+        ));
+        assert!(encoder.get_ref().is_empty());
+    }
+}
@@ -1,10 +1,17 @@
 //! Enums used in Databento APIs.
 use std::fmt::{self, Display, Formatter};
+use std::mem;
 use std::os::raw::c_char;
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use crate::Error;
+use crate::{
+    record::{
+        ConstTypeId, HasSchema, InstrumentDefMsg, MboMsg, Mbp10Msg, Mbp1Msg, OhlcvMsg, StatMsg,
+        StatusMsg, TradeMsg,
+    },
+    Duration, Error,
+};
 /// A side of the market. The side of the market for resting orders, or the side
 /// of the aggressor for trades.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -40,6 +47,52 @@ impl serde::Serialize for Side {
     }
 }
 
+impl TryFrom<char> for Side {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'A' => Ok(Side::Ask),
+            'B' => Ok(Side::Bid),
+            'N' => Ok(Side::None),
+            _ => Err(Error::TypeConversion("Value doesn't match a valid side")),
+        }
+    }
+}
+
+impl TryFrom<c_char> for Side {
+    type Error = Error;
+
+    fn try_from(c: c_char) -> Result<Self, Self::Error> {
+        Side::try_from(c as u8 as char)
+    }
+}
+
+impl std::str::FromStr for Side {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Side::try_from(c),
+            _ => Err(Error::TypeConversion("Value doesn't match a valid side")),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Side {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Side::try_from(c).map_err(|_| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Char(c),
+                &"one of 'A', 'B', or 'N'",
+            )
+        })
+    }
+}
+
 /// A tick action.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Action {
@@ -53,6 +106,12 @@ pub enum Action {
     Add,
     /// Reset the book; clear all orders for an instrument.
     Clear,
+    /// A trading status update; the book is unchanged.
+    Status,
+    /// An update to other fields; the book is unchanged.
+    Update,
+    /// An order was filled; the book is unchanged.
+    Fill,
 }
 
 impl From<Action> for char {
@@ -63,6 +122,9 @@ impl From<Action> for char {
             Action::Cancel => 'C',
             Action::Add => 'A',
             Action::Clear => 'R',
+            Action::Status => 'S',
+            Action::Update => 'U',
+            Action::Fill => 'F',
         }
     }
 }
@@ -80,12 +142,63 @@ impl serde::Serialize for Action {
     }
 }
 
+impl TryFrom<char> for Action {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'M' => Ok(Action::Modify),
+            'T' => Ok(Action::Trade),
+            'C' => Ok(Action::Cancel),
+            'A' => Ok(Action::Add),
+            'R' => Ok(Action::Clear),
+            'S' => Ok(Action::Status),
+            'U' => Ok(Action::Update),
+            'F' => Ok(Action::Fill),
+            _ => Err(Error::TypeConversion("Value doesn't match a valid action")),
+        }
+    }
+}
+
+impl TryFrom<c_char> for Action {
+    type Error = Error;
+
+    fn try_from(c: c_char) -> Result<Self, Self::Error> {
+        Action::try_from(c as u8 as char)
+    }
+}
+
+impl std::str::FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Action::try_from(c),
+            _ => Err(Error::TypeConversion("Value doesn't match a valid action")),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Action {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Action::try_from(c).map_err(|_| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Char(c),
+                &"one of 'M', 'T', 'C', 'A', 'R', 'S', 'U', or 'F'",
+            )
+        })
+    }
+}
+
 /// A symbology type. Refer to the [symbology documentation](https://docs.databento.com/reference-historical/basics/symbology)
 /// for more information.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, TryFromPrimitive)]
 #[cfg_attr(
     feature = "serde",
-    derive(serde::Serialize),
+    derive(serde::Serialize, serde::Deserialize),
     serde(rename_all = "snake_case")
 )]
 #[repr(u8)]
@@ -154,7 +267,7 @@ pub enum Schema {
     Ohlcv1D = 8,
     /// Symbol definitions.
     Definition = 9,
-    ///
+    /// Additional data disseminated by publishers.
     Statistics = 10,
     /// Exchange status.
     Status = 11,
@@ -200,6 +313,69 @@ impl Schema {
             Schema::Status => "status",
         }
     }
+
+    /// Returns the bar interval of an OHLCV schema, or `None` for all other schemas.
+    pub fn ohlcv_interval(&self) -> Option<Duration> {
+        const NANOS_PER_SEC: i64 = 1_000_000_000;
+        match self {
+            Schema::Ohlcv1S => Some(Duration::from_nanos(NANOS_PER_SEC)),
+            Schema::Ohlcv1M => Some(Duration::from_nanos(60 * NANOS_PER_SEC)),
+            Schema::Ohlcv1H => Some(Duration::from_nanos(60 * 60 * NANOS_PER_SEC)),
+            Schema::Ohlcv1D => Some(Duration::from_nanos(24 * 60 * 60 * NANOS_PER_SEC)),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`RecordHeader::rtype`](crate::record::RecordHeader::rtype) of the
+    /// records in the schema.
+    pub fn rtype(&self) -> u8 {
+        match self {
+            Schema::Mbo => MboMsg::TYPE_ID,
+            Schema::Mbp1 | Schema::Tbbo => Mbp1Msg::TYPE_ID,
+            Schema::Mbp10 => Mbp10Msg::TYPE_ID,
+            Schema::Trades => TradeMsg::TYPE_ID,
+            Schema::Ohlcv1S | Schema::Ohlcv1M | Schema::Ohlcv1H | Schema::Ohlcv1D => {
+                OhlcvMsg::TYPE_ID
+            }
+            Schema::Definition => InstrumentDefMsg::TYPE_ID,
+            Schema::Statistics => StatMsg::TYPE_ID,
+            Schema::Status => StatusMsg::TYPE_ID,
+        }
+    }
+
+    /// Returns the size in bytes of the records in the schema.
+    pub fn record_size(&self) -> usize {
+        match self {
+            Schema::Mbo => mem::size_of::<MboMsg>(),
+            Schema::Mbp1 | Schema::Tbbo => mem::size_of::<Mbp1Msg>(),
+            Schema::Mbp10 => mem::size_of::<Mbp10Msg>(),
+            Schema::Trades => mem::size_of::<TradeMsg>(),
+            Schema::Ohlcv1S | Schema::Ohlcv1M | Schema::Ohlcv1H | Schema::Ohlcv1D => {
+                mem::size_of::<OhlcvMsg>()
+            }
+            Schema::Definition => mem::size_of::<InstrumentDefMsg>(),
+            Schema::Statistics => mem::size_of::<StatMsg>(),
+            Schema::Status => mem::size_of::<StatusMsg>(),
+        }
+    }
+
+    /// Returns the schemas whose records have the given
+    /// [`RecordHeader::rtype`](crate::record::RecordHeader::rtype). Several schemas
+    /// can share a record type, e.g. all OHLCV schemas use
+    /// [`OhlcvMsg`]. Returns an empty slice if `rtype` doesn't belong to any schema.
+    pub fn from_rtype(rtype: u8) -> &'static [Schema] {
+        match rtype {
+            MboMsg::TYPE_ID => MboMsg::SCHEMAS,
+            Mbp1Msg::TYPE_ID => Mbp1Msg::SCHEMAS,
+            Mbp10Msg::TYPE_ID => Mbp10Msg::SCHEMAS,
+            TradeMsg::TYPE_ID => TradeMsg::SCHEMAS,
+            OhlcvMsg::TYPE_ID => OhlcvMsg::SCHEMAS,
+            InstrumentDefMsg::TYPE_ID => InstrumentDefMsg::SCHEMAS,
+            StatMsg::TYPE_ID => StatMsg::SCHEMAS,
+            StatusMsg::TYPE_ID => StatusMsg::SCHEMAS,
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -209,6 +385,15 @@ impl serde::Serialize for Schema {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Schema {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl Display for Schema {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(self.as_str())
@@ -219,7 +404,7 @@ impl Display for Schema {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, TryFromPrimitive)]
 #[cfg_attr(
     feature = "serde",
-    derive(serde::Serialize),
+    derive(serde::Serialize, serde::Deserialize),
     serde(rename_all = "lowercase")
 )]
 #[repr(u8)]
@@ -268,7 +453,7 @@ impl Display for Encoding {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, TryFromPrimitive)]
 #[cfg_attr(
     feature = "serde",
-    derive(serde::Serialize),
+    derive(serde::Serialize, serde::Deserialize),
     serde(rename_all = "lowercase")
 )]
 #[repr(u8)]
@@ -309,8 +494,7 @@ impl Display for Compression {
 }
 
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoPrimitive)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[doc(hidden)]
 pub enum SecurityUpdateAction {
     Add = b'A',
@@ -319,3 +503,648 @@ pub enum SecurityUpdateAction {
     // Deprecated, but still present in legacy files
     Invalid = b'~',
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SecurityUpdateAction {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(u8::from(*self) as char)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecurityUpdateAction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        u8::try_from(c)
+            .ok()
+            .and_then(|b| SecurityUpdateAction::try_from(b).ok())
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Char(c),
+                    &"one of 'A', 'M', 'D', or '~'",
+                )
+            })
+    }
+}
+
+/// The type of statistic contained in a [`StatMsg`].
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u16", try_from = "u16")
+)]
+pub enum StatType {
+    /// The price of the first trade of an instrument. `price` will be set.
+    OpeningPrice = 1,
+    /// The probable price of the first trade of an instrument published during
+    /// pre-open. Both `price` and `quantity` will be set.
+    IndicativeOpeningPrice = 2,
+    /// The settlement price of an instrument. `price` will be set and `stat_flags`
+    /// indicate whether the price is final or preliminary and actual or theoretical.
+    SettlementPrice = 3,
+    /// The lowest trade price of an instrument during the trading session. `price`
+    /// will be set.
+    TradingSessionLowPrice = 4,
+    /// The highest trade price of an instrument during the trading session. `price`
+    /// will be set.
+    TradingSessionHighPrice = 5,
+    /// The number of contracts cleared for an instrument on the previous trading date.
+    /// `quantity` will be set.
+    ClearedVolume = 6,
+    /// The lowest offer price for an instrument during the trading session. `price`
+    /// will be set.
+    LowestOffer = 7,
+    /// The highest bid price for an instrument during the trading session. `price`
+    /// will be set.
+    HighestBid = 8,
+    /// The current number of outstanding contracts of an instrument. `quantity` will
+    /// be set.
+    OpenInterest = 9,
+    /// The volume-weighted average price (VWAP) for a fixing period. `price` will be
+    /// set.
+    FixingPrice = 10,
+}
+
+/// Whether a [`StatMsg`] contains a new statistic or deletes a previous one.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum StatUpdateAction {
+    /// A new statistic.
+    New = 1,
+    /// A removal of a previously published statistic.
+    Delete = 2,
+}
+
+/// The matching algorithm used for an instrument, from the `match_algorithm` field of
+/// an [`InstrumentDefMsg`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+pub enum MatchAlgorithm {
+    /// First-in-first-out matching.
+    Fifo = b'F',
+    /// A configurable match algorithm.
+    Configurable = b'K',
+    /// Trade quantity is allocated to resting orders based on a pro-rata percentage:
+    /// resting order quantity divided by total quantity.
+    ProRata = b'C',
+    /// Like [`Self::Fifo`] but with LMM allocations prior to FIFO allocations.
+    FifoLmm = b'T',
+    /// Like [`Self::ProRata`] but includes a configurable allocation to the first order
+    /// that improves the market.
+    ThresholdProRata = b'O',
+    /// Like [`Self::FifoLmm`] but includes a configurable allocation to the first order
+    /// that improves the market.
+    FifoTopLmm = b'S',
+    /// Like [`Self::ThresholdProRata`] but includes a special priority to LMMs.
+    ThresholdProRataLmm = b'Q',
+    /// Special variant used only for Eurodollar futures on CME.
+    EurodollarFutures = b'Y',
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MatchAlgorithm {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(u8::from(*self) as char)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MatchAlgorithm {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        u8::try_from(c)
+            .ok()
+            .and_then(|b| MatchAlgorithm::try_from(b).ok())
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Char(c),
+                    &"one of 'F', 'K', 'C', 'T', 'O', 'S', 'Q', or 'Y'",
+                )
+            })
+    }
+}
+
+/// The trading status of an instrument, from the `md_security_trading_status` field
+/// of an [`InstrumentDefMsg`] or the `trading_status` field of a [`StatusMsg`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum SecurityTradingStatus {
+    /// Trading is halted.
+    TradingHalt = 2,
+    /// The instrument is closed.
+    Close = 4,
+    /// A new price indication.
+    NewPriceIndication = 15,
+    /// The instrument is ready to trade.
+    ReadyToTrade = 17,
+    /// The instrument isn't available for trading.
+    NotAvailableForTrading = 18,
+    /// The status is unknown or invalid.
+    UnknownOrInvalid = 20,
+    /// The pre-open period.
+    PreOpen = 21,
+    /// The pre-cross period.
+    PreCross = 24,
+    /// The cross period.
+    Cross = 25,
+    /// The post-close period.
+    PostClose = 26,
+    /// No change from the previous status.
+    NoChange = 103,
+}
+
+impl SecurityTradingStatus {
+    /// Converts the given trading status to a `&'static str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityTradingStatus::TradingHalt => "trading_halt",
+            SecurityTradingStatus::Close => "close",
+            SecurityTradingStatus::NewPriceIndication => "new_price_indication",
+            SecurityTradingStatus::ReadyToTrade => "ready_to_trade",
+            SecurityTradingStatus::NotAvailableForTrading => "not_available_for_trading",
+            SecurityTradingStatus::UnknownOrInvalid => "unknown_or_invalid",
+            SecurityTradingStatus::PreOpen => "pre_open",
+            SecurityTradingStatus::PreCross => "pre_cross",
+            SecurityTradingStatus::Cross => "cross",
+            SecurityTradingStatus::PostClose => "post_close",
+            SecurityTradingStatus::NoChange => "no_change",
+        }
+    }
+}
+
+impl Display for SecurityTradingStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The reason for a change in trading status, from the `halt_reason` field of a
+/// [`StatusMsg`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum HaltReason {
+    /// The change follows the schedule of the instrument's group.
+    GroupSchedule = 0,
+    /// The venue intervened, e.g. to halt trading.
+    SurveillanceIntervention = 1,
+    /// A market event, such as a price limit, caused the change.
+    MarketEvent = 2,
+    /// The instrument was activated.
+    InstrumentActivation = 3,
+    /// The instrument expired.
+    InstrumentExpiration = 4,
+    /// The reason is unknown.
+    Unknown = 5,
+    /// The venue is recovering from a failure.
+    RecoveryInProcess = 6,
+}
+
+impl HaltReason {
+    /// Converts the given halt reason to a `&'static str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HaltReason::GroupSchedule => "group_schedule",
+            HaltReason::SurveillanceIntervention => "surveillance_intervention",
+            HaltReason::MarketEvent => "market_event",
+            HaltReason::InstrumentActivation => "instrument_activation",
+            HaltReason::InstrumentExpiration => "instrument_expiration",
+            HaltReason::Unknown => "unknown",
+            HaltReason::RecoveryInProcess => "recovery_in_process",
+        }
+    }
+}
+
+impl Display for HaltReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An additional event accompanying a change in trading status, from the
+/// `trading_event` field of a [`StatusMsg`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum TradingEvent {
+    /// No additional event.
+    NoEvent = 0,
+    /// Orders can't be cancelled.
+    NoCancel = 1,
+    /// Session statistics, such as the high and low prices, are reset.
+    ResetStatistics = 4,
+    /// Implied matching is enabled.
+    ImpliedMatchingOn = 5,
+    /// Implied matching is disabled.
+    ImpliedMatchingOff = 6,
+}
+
+impl TradingEvent {
+    /// Converts the given trading event to a `&'static str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradingEvent::NoEvent => "no_event",
+            TradingEvent::NoCancel => "no_cancel",
+            TradingEvent::ResetStatistics => "reset_statistics",
+            TradingEvent::ImpliedMatchingOn => "implied_matching_on",
+            TradingEvent::ImpliedMatchingOff => "implied_matching_off",
+        }
+    }
+}
+
+impl Display for TradingEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The settlement price type indicators from the `settl_price_type` field of an
+/// [`InstrumentDefMsg`].
+///
+/// Unlike most fields, `settl_price_type` is a bitmap so several indicators can apply
+/// at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SettlPriceType(u8);
+
+impl SettlPriceType {
+    const FINAL: u8 = 1;
+    const ACTUAL: u8 = 1 << 1;
+    const ROUNDED: u8 = 1 << 2;
+    const INTRADAY: u8 = 1 << 3;
+    const NULL: u8 = 1 << 7;
+
+    /// Creates settlement price type indicators from the raw value of a
+    /// `settl_price_type` field.
+    pub const fn from_raw(raw: u8) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value of the indicators.
+    pub const fn raw(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if the settlement price is final, `false` if it's preliminary.
+    pub const fn is_final(self) -> bool {
+        self.0 & Self::FINAL != 0
+    }
+
+    /// Returns `true` if the settlement price is actual, `false` if it's theoretical.
+    pub const fn is_actual(self) -> bool {
+        self.0 & Self::ACTUAL != 0
+    }
+
+    /// Returns `true` if the settlement price was rounded.
+    pub const fn is_rounded(self) -> bool {
+        self.0 & Self::ROUNDED != 0
+    }
+
+    /// Returns `true` if the settlement price is an intraday settlement.
+    pub const fn is_intraday(self) -> bool {
+        self.0 & Self::INTRADAY != 0
+    }
+
+    /// Returns `true` if there is no settlement price type.
+    pub const fn is_null(self) -> bool {
+        self.0 & Self::NULL != 0
+    }
+}
+
+impl From<u8> for SettlPriceType {
+    fn from(raw: u8) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<SettlPriceType> for u8 {
+    fn from(settl_price_type: SettlPriceType) -> Self {
+        settl_price_type.0
+    }
+}
+
+/// The product complex of an instrument's underlying, from the `underlying_product`
+/// field of an [`InstrumentDefMsg`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum UnderlyingProduct {
+    /// Commodities and agriculture.
+    CommodityAgriculture = 2,
+    /// Currencies.
+    Currency = 4,
+    /// Equities.
+    Equity = 5,
+    /// Any other product complex.
+    Other = 12,
+    /// Interest rates.
+    InterestRate = 14,
+    /// Foreign exchange cash.
+    FxCash = 15,
+    /// Energy.
+    Energy = 16,
+    /// Metals.
+    Metals = 17,
+}
+
+/// Whether an instrument is user-defined, from the `user_defined_instrument` field of
+/// an [`InstrumentDefMsg`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+pub enum UserDefinedInstrument {
+    /// The instrument isn't user-defined.
+    No = b'N',
+    /// The instrument is user-defined.
+    Yes = b'Y',
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for UserDefinedInstrument {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(u8::from(*self) as char)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UserDefinedInstrument {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        u8::try_from(c)
+            .ok()
+            .and_then(|b| UserDefinedInstrument::try_from(b).ok())
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Char(c),
+                    &"one of 'N' or 'Y'",
+                )
+            })
+    }
+}
+
+/// The type of auction an [`Imbalance`](crate::record::Imbalance) record refers to.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+pub enum AuctionType {
+    /// An opening auction.
+    Opening = b'O',
+    /// A core opening auction, e.g. the NYSE Arca core open.
+    CoreOpening = b'M',
+    /// A reopening auction following a halt.
+    HaltReopening = b'H',
+    /// A closing auction.
+    Closing = b'C',
+    /// A regulatory imbalance for the closing auction.
+    RegulatoryClosing = b'R',
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AuctionType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(u8::from(*self) as char)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AuctionType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        u8::try_from(c)
+            .ok()
+            .and_then(|b| AuctionType::try_from(b).ok())
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Char(c),
+                    &"one of 'O', 'M', 'H', 'C', or 'R'",
+                )
+            })
+    }
+}
+
+/// Whether the auction an [`Imbalance`](crate::record::Imbalance) record refers to is
+/// expected to run.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum AuctionStatus {
+    /// The auction will run.
+    WillRun = 0,
+    /// The auction will run, but the indicative price is outside the collar.
+    OutsideCollar = 1,
+    /// The auction won't run.
+    WillNotRun = 2,
+}
+
+/// Whether the order book of the auction an [`Imbalance`](crate::record::Imbalance)
+/// record refers to is frozen.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum FreezeStatus {
+    /// No freeze is in effect.
+    NotFrozen = 0,
+    /// A freeze is in effect and only orders that offset the imbalance are accepted.
+    Frozen = 1,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{GatewayErrorMsg, IMBALANCE_TYPE_ID};
+
+    const ACTIONS: [Action; 8] = [
+        Action::Modify,
+        Action::Trade,
+        Action::Cancel,
+        Action::Add,
+        Action::Clear,
+        Action::Status,
+        Action::Update,
+        Action::Fill,
+    ];
+
+    #[test]
+    fn test_side_action_round_trip() {
+        for side in [Side::Ask, Side::Bid, Side::None] {
+            assert_eq!(Side::try_from(char::from(side)).unwrap(), side);
+            assert_eq!(Side::try_from(c_char::from(side)).unwrap(), side);
+            assert_eq!(char::from(side).to_string().parse::<Side>().unwrap(), side);
+        }
+        for action in ACTIONS {
+            assert_eq!(Action::try_from(char::from(action)).unwrap(), action);
+            assert_eq!(Action::try_from(c_char::from(action)).unwrap(), action);
+            assert_eq!(
+                char::from(action).to_string().parse::<Action>().unwrap(),
+                action
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_rtype() {
+        for schema in (0..=u16::from(u8::MAX)).filter_map(|s| Schema::try_from(s).ok()) {
+            assert!(
+                Schema::from_rtype(schema.rtype()).contains(&schema),
+                "{schema}"
+            );
+        }
+        assert_eq!(Schema::Tbbo.rtype(), Mbp1Msg::TYPE_ID);
+        assert_eq!(Schema::Ohlcv1H.record_size(), 56);
+        assert_eq!(
+            Schema::from_rtype(Mbp1Msg::TYPE_ID),
+            [Schema::Mbp1, Schema::Tbbo]
+        );
+        assert_eq!(Schema::from_rtype(MboMsg::TYPE_ID), [Schema::Mbo]);
+        assert!(Schema::from_rtype(IMBALANCE_TYPE_ID).is_empty());
+        assert!(Schema::from_rtype(GatewayErrorMsg::TYPE_ID).is_empty());
+    }
+
+    #[test]
+    fn test_ohlcv_interval() {
+        assert_eq!(
+            Schema::Ohlcv1M.ohlcv_interval(),
+            Some(Duration::from_nanos(60_000_000_000))
+        );
+        assert_eq!(Schema::Trades.ohlcv_interval(), None);
+    }
+
+    #[test]
+    fn test_status_display() {
+        assert_eq!(SecurityTradingStatus::PreOpen.to_string(), "pre_open");
+        assert_eq!(
+            HaltReason::try_from(1).unwrap().to_string(),
+            "surveillance_intervention"
+        );
+        assert_eq!(TradingEvent::NoCancel.to_string(), "no_cancel");
+        assert!(TradingEvent::try_from(2).is_err());
+    }
+
+    #[test]
+    fn test_side_action_invalid() {
+        assert!(Side::try_from('a').is_err());
+        assert!(Side::try_from(0 as c_char).is_err());
+        assert!("".parse::<Side>().is_err());
+        assert!("AB".parse::<Side>().is_err());
+        assert!(Action::try_from('X').is_err());
+        assert!("Add".parse::<Action>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        for side in [Side::Ask, Side::Bid, Side::None] {
+            let json = serde_json::to_string(&side).unwrap();
+            assert_eq!(serde_json::from_str::<Side>(&json).unwrap(), side);
+        }
+        for action in ACTIONS {
+            let json = serde_json::to_string(&action).unwrap();
+            assert_eq!(serde_json::from_str::<Action>(&json).unwrap(), action);
+        }
+        let json = serde_json::to_string(&Schema::Ohlcv1M).unwrap();
+        assert_eq!(json, "\"ohlcv-1m\"");
+        assert_eq!(
+            serde_json::from_str::<Schema>(&json).unwrap(),
+            Schema::Ohlcv1M
+        );
+        let json = serde_json::to_string(&SType::ProductId).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SType>(&json).unwrap(),
+            SType::ProductId
+        );
+        let json = serde_json::to_string(&StatType::OpenInterest).unwrap();
+        assert_eq!(json, "9");
+        assert_eq!(
+            serde_json::from_str::<StatType>(&json).unwrap(),
+            StatType::OpenInterest
+        );
+        let json = serde_json::to_string(&MatchAlgorithm::ProRata).unwrap();
+        assert_eq!(json, "\"C\"");
+        assert_eq!(
+            serde_json::from_str::<MatchAlgorithm>(&json).unwrap(),
+            MatchAlgorithm::ProRata
+        );
+        let json = serde_json::to_string(&UnderlyingProduct::InterestRate).unwrap();
+        assert_eq!(json, "14");
+        assert_eq!(
+            serde_json::from_str::<UnderlyingProduct>(&json).unwrap(),
+            UnderlyingProduct::InterestRate
+        );
+        let json = serde_json::to_string(&HaltReason::MarketEvent).unwrap();
+        assert_eq!(json, "2");
+        assert_eq!(
+            serde_json::from_str::<HaltReason>(&json).unwrap(),
+            HaltReason::MarketEvent
+        );
+        let json = serde_json::to_string(&AuctionType::Closing).unwrap();
+        assert_eq!(json, "\"C\"");
+        assert_eq!(
+            serde_json::from_str::<AuctionType>(&json).unwrap(),
+            AuctionType::Closing
+        );
+        let json = serde_json::to_string(&FreezeStatus::Frozen).unwrap();
+        assert_eq!(json, "1");
+        assert_eq!(
+            serde_json::from_str::<FreezeStatus>(&json).unwrap(),
+            FreezeStatus::Frozen
+        );
+        let json = serde_json::to_string(&SecurityUpdateAction::Modify).unwrap();
+        assert_eq!(json, "\"M\"");
+        assert_eq!(
+            serde_json::from_str::<SecurityUpdateAction>(&json).unwrap(),
+            SecurityUpdateAction::Modify
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(serde_json::from_str::<Side>("\"X\"").is_err());
+        assert!(serde_json::from_str::<Action>("\"X\"").is_err());
+        assert!(serde_json::from_str::<Schema>("\"mbp-5\"").is_err());
+        assert!(serde_json::from_str::<SecurityUpdateAction>("\"Z\"").is_err());
+        assert!(serde_json::from_str::<StatType>("0").is_err());
+        assert!(serde_json::from_str::<StatUpdateAction>("3").is_err());
+        assert!(serde_json::from_str::<MatchAlgorithm>("\"X\"").is_err());
+        assert!(serde_json::from_str::<SecurityTradingStatus>("3").is_err());
+        assert!(serde_json::from_str::<HaltReason>("7").is_err());
+        assert!(serde_json::from_str::<TradingEvent>("2").is_err());
+        assert!(serde_json::from_str::<AuctionType>("\"X\"").is_err());
+        assert!(serde_json::from_str::<AuctionStatus>("3").is_err());
+        assert!(serde_json::from_str::<UserDefinedInstrument>("\"y\"").is_err());
+    }
+}
@@ -1,8 +1,8 @@
 //! Types for errors that can occur in databento-defs and dependent crates.
-use std::{ffi::NulError, fmt::Display, num::TryFromIntError};
+use std::{ffi::NulError, fmt::Display, io, num::TryFromIntError};
 
 /// Simple error type for failed conversions.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum Error {
     /// Received an unexpected `NULL` back from an FFI function.
     NullPointer,
@@ -10,6 +10,16 @@ pub enum Error {
     TypeConversion(&'static str),
     /// A file that was expected to exist does not.
     FileDoesNotExist(String),
+    /// Bytes couldn't be interpreted as a valid record.
+    Decode(String),
+    /// A record couldn't be encoded.
+    Encode(String),
+    /// Encountered a record with an unrecognized `rtype`.
+    UnknownRType(u8),
+    /// An I/O error while reading or writing records.
+    Io(io::Error),
+    /// A record couldn't be applied to an order book.
+    Book(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -20,11 +30,29 @@ impl Display for Error {
             Error::NullPointer => write!(f, "Received unexpected NULL from the FFI"),
             Error::TypeConversion(msg) => write!(f, "Type conversion error: {msg}"),
             Error::FileDoesNotExist(path) => write!(f, "Path doesn't exist: {path}"),
+            Error::Decode(msg) => write!(f, "Decoding error: {msg}"),
+            Error::Encode(msg) => write!(f, "Encoding error: {msg}"),
+            Error::UnknownRType(rtype) => write!(f, "Unknown rtype: {rtype:#04X}"),
+            Error::Io(err) => write!(f, "IO error: {err}"),
+            Error::Book(msg) => write!(f, "Order book error: {msg}"),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
 
 impl From<NulError> for Error {
     fn from(_: NulError) -> Self {
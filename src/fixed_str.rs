@@ -0,0 +1,179 @@
+//! A fixed-capacity string type for the text fields of records.
+use std::{borrow::Cow, fmt, os::raw::c_char};
+
+use crate::{Error, Result};
+
+/// A NUL-padded string of at most `N - 1` bytes stored inline with the same layout as
+/// `[c_char; N]`, as used for text fields such as symbols in records.
+///
+/// Reading the text never relies on a NUL terminator being present: it ends at the
+/// first NUL byte or at the end of the array, whichever comes first.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedStr<const N: usize>([c_char; N]);
+
+impl<const N: usize> FixedStr<N> {
+    /// Creates a string from the raw value of a `c_char` array.
+    pub const fn from_raw(raw: [c_char; N]) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw `c_char` array.
+    pub const fn raw(&self) -> &[c_char; N] {
+        &self.0
+    }
+
+    /// Returns the bytes of the string up to but excluding the first NUL byte.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: `c_char` and `u8` have the same size and alignment
+        let bytes = unsafe { std::slice::from_raw_parts(self.0.as_ptr() as *const u8, N) };
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(N);
+        &bytes[..len]
+    }
+
+    /// Returns the string as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if the string isn't valid UTF-8.
+    pub fn as_str(&self) -> Result<&str> {
+        std::str::from_utf8(self.as_bytes())
+            .map_err(|_| Error::TypeConversion("Invalid UTF-8 in c_char array"))
+    }
+
+    /// Returns the string, replacing any invalid UTF-8 sequences with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+
+    /// Returns `true` if the string has a length of zero.
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+}
+
+impl<const N: usize> Default for FixedStr<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> From<[c_char; N]> for FixedStr<N> {
+    fn from(raw: [c_char; N]) -> Self {
+        Self(raw)
+    }
+}
+
+impl<const N: usize> From<FixedStr<N>> for [c_char; N] {
+    fn from(s: FixedStr<N>) -> Self {
+        s.0
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for FixedStr<N> {
+    type Error = Error;
+
+    /// Copies `s` into a new string, which must be shorter than `N` bytes to leave room
+    /// for a NUL terminator and can't contain any NUL bytes.
+    fn try_from(s: &str) -> Result<Self> {
+        if s.len() >= N {
+            return Err(Error::TypeConversion(
+                "String is too long for the fixed-length c_char array",
+            ));
+        }
+        if s.contains('\0') {
+            return Err(Error::TypeConversion(
+                "String for a fixed-length c_char array contains a NUL byte",
+            ));
+        }
+        let mut arr = [0; N];
+        for (c, b) in arr.iter_mut().zip(s.bytes()) {
+            *c = b as c_char;
+        }
+        Ok(Self(arr))
+    }
+}
+
+/// Formats the string, replacing any invalid UTF-8 sequences.
+impl<const N: usize> fmt::Display for FixedStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_lossy())
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for FixedStr<N> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string_lossy())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for FixedStr<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        if s.len() >= N {
+            return Err(serde::de::Error::invalid_length(
+                s.len(),
+                &format!("a string shorter than {N} bytes").as_str(),
+            ));
+        }
+        Self::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_str() {
+        let s = FixedStr::<8>::try_from("ESZ2").unwrap();
+        assert_eq!(s.as_str().unwrap(), "ESZ2");
+        assert_eq!(s.to_string(), "ESZ2");
+        assert_eq!(format!("{s:?}"), "\"ESZ2\"");
+        assert!(FixedStr::<8>::default().is_empty());
+        assert!(!s.is_empty());
+    }
+
+    #[test]
+    fn test_without_nul() {
+        let s = FixedStr::from_raw([b'E', b'S', b'Z', b'2'].map(|b| b as c_char));
+        assert_eq!(s.as_str().unwrap(), "ESZ2");
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let s = FixedStr::from_raw([b'E' as c_char, 0xFF_u8 as c_char, 0]);
+        assert!(s.as_str().is_err());
+        assert_eq!(s.to_string(), "E\u{FFFD}");
+    }
+
+    #[test]
+    fn test_try_from_too_long() {
+        assert!(FixedStr::<4>::try_from("USD").is_ok());
+        assert!(FixedStr::<4>::try_from("USDT").is_err());
+        assert!(FixedStr::<4>::try_from("U\0D").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let s = FixedStr::<8>::try_from("ESZ2").unwrap();
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, "\"ESZ2\"");
+        assert_eq!(serde_json::from_str::<FixedStr<8>>(&json).unwrap(), s);
+        assert!(serde_json::from_str::<FixedStr<4>>("\"ESZ2\"").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Bit flags for the `flags` field of records.
+use std::{fmt, ops};
+
+/// A set of bit flags describing a record, such as whether it's the last record in a
+/// packet from the venue.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Flags(u8);
+
+impl Flags {
+    /// Indicates the last record in the packet from the venue for a given
+    /// `product_id`.
+    pub const LAST: Self = Self(1 << 7);
+    /// Indicates a top-of-book record, not an individual order.
+    pub const TOB: Self = Self(1 << 6);
+    /// Indicates the record was sourced from a replay, such as a snapshot server.
+    pub const SNAPSHOT: Self = Self(1 << 5);
+    /// Indicates an aggregated price level record, not an individual order.
+    pub const MBP: Self = Self(1 << 4);
+    /// Indicates the `ts_recv` value is inaccurate due to clock issues or packet
+    /// reordering.
+    pub const BAD_TS_RECV: Self = Self(1 << 3);
+    /// Indicates an unrecoverable gap was detected in the channel.
+    pub const MAYBE_BAD_BOOK: Self = Self(1 << 2);
+
+    const NAMED: [(Self, &'static str); 6] = [
+        (Self::LAST, "LAST"),
+        (Self::TOB, "TOB"),
+        (Self::SNAPSHOT, "SNAPSHOT"),
+        (Self::MBP, "MBP"),
+        (Self::BAD_TS_RECV, "BAD_TS_RECV"),
+        (Self::MAYBE_BAD_BOOK, "MAYBE_BAD_BOOK"),
+    ];
+
+    /// Returns an empty set of flags.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a set of flags from the raw value of a `flags` field.
+    pub const fn from_raw(raw: u8) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value of the flags.
+    pub const fn raw(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if no flags are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all flags in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets the flags in `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clears the flags in `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// Sets or clears the flags in `other` depending on `value`.
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+
+    /// Returns `true` if [`Flags::LAST`] is set.
+    pub const fn is_last(self) -> bool {
+        self.contains(Self::LAST)
+    }
+
+    /// Returns `true` if [`Flags::TOB`] is set.
+    pub const fn is_tob(self) -> bool {
+        self.contains(Self::TOB)
+    }
+
+    /// Returns `true` if [`Flags::SNAPSHOT`] is set.
+    pub const fn is_snapshot(self) -> bool {
+        self.contains(Self::SNAPSHOT)
+    }
+
+    /// Returns `true` if [`Flags::MBP`] is set.
+    pub const fn is_mbp(self) -> bool {
+        self.contains(Self::MBP)
+    }
+
+    /// Returns `true` if [`Flags::BAD_TS_RECV`] is set.
+    pub const fn is_bad_ts_recv(self) -> bool {
+        self.contains(Self::BAD_TS_RECV)
+    }
+
+    /// Returns `true` if [`Flags::MAYBE_BAD_BOOK`] is set.
+    pub const fn is_maybe_bad_book(self) -> bool {
+        self.contains(Self::MAYBE_BAD_BOOK)
+    }
+}
+
+impl From<u8> for Flags {
+    fn from(raw: u8) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Flags> for u8 {
+    fn from(flags: Flags) -> Self {
+        flags.0
+    }
+}
+
+impl ops::BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitOrAssign for Flags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+impl ops::BitAnd for Flags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl ops::BitAndAssign for Flags {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl ops::Not for Flags {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+/// Lists the names of the set flags separated by ` | `, followed by any unnamed bits
+/// in hexadecimal. Empty flags are formatted as an empty string.
+impl fmt::Display for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut remaining = *self;
+        let mut is_first = true;
+        let mut write_sep = |f: &mut fmt::Formatter<'_>| {
+            if is_first {
+                is_first = false;
+                Ok(())
+            } else {
+                f.write_str(" | ")
+            }
+        };
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                write_sep(f)?;
+                f.write_str(name)?;
+                remaining.remove(flag);
+            }
+        }
+        if !remaining.is_empty() {
+            write_sep(f)?;
+            write!(f, "{:#x}", remaining.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            write!(f, "Flags({:#x})", self.0)
+        } else {
+            write!(f, "Flags({self})")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let mut flags = Flags::from_raw(0b1010_0000);
+        assert!(flags.is_last());
+        assert!(flags.is_snapshot());
+        assert!(!flags.is_tob());
+        assert!(!flags.is_mbp());
+        assert!(flags.contains(Flags::LAST | Flags::SNAPSHOT));
+        flags.set(Flags::SNAPSHOT, false);
+        flags |= Flags::BAD_TS_RECV;
+        assert_eq!(flags, Flags::LAST | Flags::BAD_TS_RECV);
+        assert_eq!(u8::from(flags), 0b1000_1000);
+        assert_eq!(flags & !Flags::LAST, Flags::BAD_TS_RECV);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Flags::empty().to_string(), "");
+        assert_eq!(
+            (Flags::LAST | Flags::MAYBE_BAD_BOOK).to_string(),
+            "LAST | MAYBE_BAD_BOOK"
+        );
+        assert_eq!(Flags::from_raw(0b0100_0011).to_string(), "TOB | 0x3");
+        assert_eq!(format!("{:?}", Flags::TOB | Flags::MBP), "Flags(TOB | MBP)");
+        assert_eq!(format!("{:?}", Flags::empty()), "Flags(0x0)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let flags = Flags::LAST | Flags::SNAPSHOT;
+        assert_eq!(serde_json::to_string(&flags).unwrap(), "160");
+        assert_eq!(serde_json::from_str::<Flags>("160").unwrap(), flags);
+    }
+}
@@ -1,10 +1,42 @@
 //! A crate containing common Databento enums and data structs.
+pub mod compression;
+pub mod csv;
+pub mod decode;
+pub mod encode;
 #[deny(missing_docs)] // document or use #[doc(hidden)]
 #[deny(rustdoc::broken_intra_doc_links)]
 #[deny(clippy::missing_errors_doc)]
 #[forbid(unsafe_code)]
 pub mod enums;
 pub mod error;
+pub mod fixed_str;
+pub mod flags;
+pub mod market;
+pub mod mbp;
+pub mod metadata;
+pub mod ohlcv;
+pub mod order_book;
+pub mod price;
 pub mod record;
+pub mod record_enum;
+pub mod record_ref;
+pub mod symbol_map;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod timestamp;
 
+pub use decode::RecordDecoder;
+pub use encode::RecordEncoder;
 pub use error::{Error, Result};
+pub use fixed_str::FixedStr;
+pub use flags::Flags;
+pub use market::Market;
+pub use mbp::{MbpBuilder, MbpUpdate, TbboBuilder};
+pub use metadata::Metadata;
+pub use ohlcv::{EmptyIntervals, OhlcvAggregator, OhlcvResampler};
+pub use order_book::OrderBook;
+pub use price::Price;
+pub use record_enum::{RecordEnum, RecordRefEnum};
+pub use record_ref::RecordRef;
+pub use symbol_map::SymbolMap;
+pub use timestamp::{Duration, Timestamp};
@@ -0,0 +1,227 @@
+//! Order books for many instruments across publishers.
+use std::collections::HashMap;
+
+use crate::{
+    order_book::{OrderBook, PriceLevel},
+    record::MboMsg,
+    Price, Result,
+};
+
+/// A collection of [`OrderBook`]s, one per combination of
+/// [`publisher_id`](crate::record::RecordHeader::publisher_id) and
+/// [`product_id`](crate::record::RecordHeader::product_id), built by applying
+/// [`MboMsg`] records in order.
+///
+/// Books for the same `product_id` from different publishers can be combined into a
+/// consolidated view with [`Market::consolidated_bbo`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Market {
+    // Keyed by product ID first so consolidated queries only visit one instrument
+    books: HashMap<u32, HashMap<u16, OrderBook>>,
+}
+
+impl Market {
+    /// Creates a new market with no books.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies an MBO record to the book for its publisher and product, creating the
+    /// book if necessary. An [`Action::Clear`](crate::enums::Action::Clear) only
+    /// clears the book for that publisher and product.
+    ///
+    /// # Errors
+    /// This function returns an error if the record can't be applied to the book. See
+    /// [`OrderBook::apply`].
+    pub fn apply(&mut self, mbo: &MboMsg) -> Result<()> {
+        let books = self.books.entry(mbo.hd.product_id).or_default();
+        if let Some(book) = books.get_mut(&mbo.hd.publisher_id) {
+            return book.apply(mbo);
+        }
+        // Only keep a new book once a record has been applied successfully
+        let mut book = OrderBook::new();
+        let res = book.apply(mbo);
+        if res.is_ok() {
+            books.insert(mbo.hd.publisher_id, book);
+        } else if books.is_empty() {
+            self.books.remove(&mbo.hd.product_id);
+        }
+        res
+    }
+
+    /// Returns the book for `product_id` from `publisher_id`, if any records have been
+    /// applied for it.
+    pub fn book(&self, publisher_id: u16, product_id: u32) -> Option<&OrderBook> {
+        self.books.get(&product_id)?.get(&publisher_id)
+    }
+
+    /// Returns an iterator over the publisher IDs and books for `product_id`.
+    pub fn books(&self, product_id: u32) -> impl Iterator<Item = (u16, &OrderBook)> {
+        self.books.get(&product_id).into_iter().flat_map(|books| {
+            books
+                .iter()
+                .map(|(&publisher_id, book)| (publisher_id, book))
+        })
+    }
+
+    /// Returns the best bid and offer for `product_id` from `publisher_id`.
+    pub fn bbo(
+        &self,
+        publisher_id: u16,
+        product_id: u32,
+    ) -> (Option<PriceLevel>, Option<PriceLevel>) {
+        self.book(publisher_id, product_id)
+            .map_or((None, None), OrderBook::bbo)
+    }
+
+    /// Returns the best bid and offer for `product_id` across all publishers. The
+    /// size and count of each level are the totals across all publishers at the best
+    /// price.
+    pub fn consolidated_bbo(&self, product_id: u32) -> (Option<PriceLevel>, Option<PriceLevel>) {
+        let mut best_bid: Option<PriceLevel> = None;
+        let mut best_ask: Option<PriceLevel> = None;
+        for (_, book) in self.books(product_id) {
+            let (bid, ask) = book.bbo();
+            best_bid = consolidate(best_bid, bid, |new, best| new > best);
+            best_ask = consolidate(best_ask, ask, |new, best| new < best);
+        }
+        (best_bid, best_ask)
+    }
+
+    /// Removes all orders from every book.
+    pub fn clear(&mut self) {
+        self.books
+            .values_mut()
+            .flat_map(HashMap::values_mut)
+            .for_each(OrderBook::clear);
+    }
+}
+
+/// Combines the best level so far with a publisher's best level, where `is_better`
+/// compares a new price to the best price so far.
+fn consolidate(
+    best: Option<PriceLevel>,
+    level: Option<PriceLevel>,
+    is_better: fn(Price, Price) -> bool,
+) -> Option<PriceLevel> {
+    match (best, level) {
+        (Some(best), Some(level)) if level.price == best.price => Some(PriceLevel {
+            price: best.price,
+            size: best.size.saturating_add(level.size),
+            count: best.count.saturating_add(level.count),
+        }),
+        (Some(best), Some(level)) if is_better(level.price, best.price) => Some(level),
+        (None, level) => level,
+        (best, _) => best,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        enums::{Action, Side},
+        record::RecordHeader,
+        test_utils,
+    };
+
+    fn mbo(
+        publisher_id: u16,
+        product_id: u32,
+        action: Action,
+        side: Side,
+        order_id: u64,
+        price: i64,
+        size: u32,
+    ) -> MboMsg {
+        let mbo = test_utils::mbo(action, side, order_id, price, size);
+        MboMsg {
+            hd: RecordHeader::new::<MboMsg>(publisher_id, product_id, mbo.hd.ts_event),
+            ..mbo
+        }
+    }
+
+    fn level(price: i64, size: u32, count: u32) -> PriceLevel {
+        PriceLevel {
+            price: Price::from_raw(price),
+            size,
+            count,
+        }
+    }
+
+    fn market() -> Market {
+        let mut market = Market::new();
+        for rec in [
+            mbo(1, 10, Action::Add, Side::Bid, 1, 100, 5),
+            mbo(1, 10, Action::Add, Side::Ask, 2, 102, 5),
+            mbo(2, 10, Action::Add, Side::Bid, 1, 100, 3),
+            mbo(2, 10, Action::Add, Side::Ask, 2, 101, 1),
+            mbo(2, 10, Action::Add, Side::Ask, 3, 101, 2),
+            mbo(1, 11, Action::Add, Side::Bid, 1, 50, 7),
+        ] {
+            market.apply(&rec).unwrap();
+        }
+        market
+    }
+
+    #[test]
+    fn test_routing() {
+        let market = market();
+        assert_eq!(
+            market.bbo(1, 10),
+            (Some(level(100, 5, 1)), Some(level(102, 5, 1)))
+        );
+        assert_eq!(
+            market.bbo(2, 10),
+            (Some(level(100, 3, 1)), Some(level(101, 3, 2)))
+        );
+        assert_eq!(market.bbo(1, 11), (Some(level(50, 7, 1)), None));
+        assert_eq!(market.bbo(2, 11), (None, None));
+        assert!(market.book(3, 10).is_none());
+        let mut publishers = market.books(10).map(|(id, _)| id).collect::<Vec<_>>();
+        publishers.sort_unstable();
+        assert_eq!(publishers, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_consolidated_bbo() {
+        let market = market();
+        assert_eq!(
+            market.consolidated_bbo(10),
+            (Some(level(100, 8, 2)), Some(level(101, 3, 2)))
+        );
+        assert_eq!(market.consolidated_bbo(11), (Some(level(50, 7, 1)), None));
+        assert_eq!(market.consolidated_bbo(12), (None, None));
+    }
+
+    #[test]
+    fn test_clear_single_instrument() {
+        let mut market = market();
+        market
+            .apply(&mbo(2, 10, Action::Clear, Side::None, 0, 0, 0))
+            .unwrap();
+        assert!(market.book(2, 10).unwrap().is_empty());
+        assert!(!market.book(1, 10).unwrap().is_empty());
+        assert!(!market.book(1, 11).unwrap().is_empty());
+        assert_eq!(
+            market.consolidated_bbo(10),
+            (Some(level(100, 5, 1)), Some(level(102, 5, 1)))
+        );
+        market.clear();
+        assert_eq!(market.consolidated_bbo(11), (None, None));
+    }
+
+    #[test]
+    fn test_failed_first_record() {
+        let mut market = market();
+        assert!(market
+            .apply(&mbo(3, 10, Action::Cancel, Side::Bid, 99, 100, 1))
+            .is_err());
+        assert!(market.book(3, 10).is_none());
+        assert!(market
+            .apply(&mbo(1, 12, Action::Cancel, Side::Bid, 99, 100, 1))
+            .is_err());
+        assert!(market.book(1, 12).is_none());
+        assert_eq!(market.books(12).count(), 0);
+    }
+}
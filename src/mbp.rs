@@ -0,0 +1,419 @@
+//! Derivation of market-by-price (MBP) and trade-space top-of-book (TBBO) records from
+//! a stream of [`MboMsg`] records.
+use std::os::raw::c_char;
+
+use crate::{
+    enums::{Action, Side},
+    order_book::OrderBook,
+    record::{BidAskPair, ConstTypeId, MboMsg, Mbp10Msg, Mbp1Msg, RecordHeader, TbboMsg},
+    Error, Price, Result,
+};
+
+/// A market-by-price update with a book depth of `N`, equivalent to [`Mbp1Msg`] or
+/// [`Mbp10Msg`] for `N` of 1 or 10, into which it can be converted. Other depths have
+/// no corresponding record type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MbpUpdate<const N: usize> {
+    /// The publisher ID assigned by Databento.
+    pub publisher_id: u16,
+    /// The product ID assigned by the venue.
+    pub product_id: u32,
+    /// The matching engine received timestamp expressed as number of nanoseconds since
+    /// UNIX epoch.
+    pub ts_event: u64,
+    /// The price of the event.
+    pub price: Price,
+    /// The quantity of the event.
+    pub size: u32,
+    /// The event action.
+    pub action: Action,
+    /// The side of the book that changed, or the aggressor side for trades.
+    pub side: Side,
+    /// The flags of the MBO record that caused the update.
+    pub flags: u8,
+    /// The level of the book that changed, starting at 0 for the top of book.
+    pub depth: u8,
+    /// The capture server received timestamp expressed as number of nanoseconds since
+    /// UNIX epoch.
+    pub ts_recv: u64,
+    /// The delta of `ts_recv - ts_exchange_send`, max 2 seconds.
+    pub ts_in_delta: i32,
+    /// The message sequence number assigned at the venue.
+    pub sequence: u32,
+    /// The top `N` levels of the book after the event.
+    pub booklevel: [BidAskPair; N],
+}
+
+/// Builds an order book from [`MboMsg`] records and emits an [`MbpUpdate`] for every
+/// trade and every book change within the top `N` levels, as defined by the
+/// [`Mbp1`](crate::enums::Schema::Mbp1) and [`Mbp10`](crate::enums::Schema::Mbp10)
+/// schemas.
+///
+/// [`MbpUpdate::depth`] is a `u8`, so `N` should be at most 256: changes to deeper
+/// levels can't be reported and cause [`MbpBuilder::apply`] to return an error.
+#[derive(Clone, Debug)]
+pub struct MbpBuilder<const N: usize> {
+    book: OrderBook,
+    levels: [BidAskPair; N],
+}
+
+impl<const N: usize> MbpBuilder<N> {
+    /// Creates a new builder with an empty book.
+    pub fn new() -> Self {
+        let book = OrderBook::new();
+        let levels = book.top_levels();
+        Self { book, levels }
+    }
+
+    /// Returns the underlying order book.
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Applies an MBO record to the book, returning an update if the record was a
+    /// trade, cleared the book, or changed the top `N` levels of the book.
+    ///
+    /// # Errors
+    /// This function returns an error if the record can't be applied to the book (see
+    /// [`OrderBook::apply`]) or the depth of the changed level can't be determined or
+    /// doesn't fit in a `u8`.
+    pub fn apply(&mut self, mbo: &MboMsg) -> Result<Option<MbpUpdate<N>>> {
+        let action = mbo.action()?;
+        let prev_price = self.book.order(mbo.order_id).map(|order| order.price);
+        self.book.apply(mbo)?;
+        let (side, price, depth) = match action {
+            Action::Add | Action::Modify | Action::Cancel => {
+                let levels = self.book.top_levels();
+                if levels == self.levels {
+                    return Ok(None);
+                }
+                let side = mbo.side()?;
+                // Find the changed level in the new book, falling back to the old book
+                // for levels that were removed
+                let depth = level_index(&levels, side, mbo.price())
+                    .or_else(|| level_index(&self.levels, side, prev_price?));
+                self.levels = levels;
+                let depth = depth.ok_or_else(|| {
+                    Error::Book(format!(
+                        "Unable to determine the depth of the changed {side:?} level at {}",
+                        mbo.price()
+                    ))
+                })?;
+                (side, mbo.price(), depth)
+            }
+            Action::Trade => (mbo.side()?, mbo.price(), 0),
+            Action::Clear => {
+                self.levels = self.book.top_levels();
+                (Side::None, Price::UNDEF, 0)
+            }
+            Action::Fill | Action::Status | Action::Update => return Ok(None),
+        };
+        Ok(Some(MbpUpdate {
+            publisher_id: mbo.hd.publisher_id,
+            product_id: mbo.hd.product_id,
+            ts_event: mbo.hd.ts_event,
+            price,
+            size: if action == Action::Clear { 0 } else { mbo.size },
+            action,
+            side,
+            flags: mbo.flags,
+            depth,
+            ts_recv: mbo.ts_recv,
+            ts_in_delta: mbo.ts_in_delta,
+            sequence: mbo.sequence,
+            // Records are only `Copy` with the `trivial_copy` feature
+            #[allow(clippy::clone_on_copy)]
+            booklevel: self.levels.clone(),
+        }))
+    }
+}
+
+impl<const N: usize> Default for MbpBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds an order book from [`MboMsg`] records and emits a [`TbboMsg`] for every
+/// trade with the best bid and offer immediately before the trade, as defined by the
+/// [`Tbbo`](crate::enums::Schema::Tbbo) schema.
+#[derive(Clone, Debug, Default)]
+pub struct TbboBuilder {
+    book: OrderBook,
+}
+
+impl TbboBuilder {
+    /// Creates a new builder with an empty book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the underlying order book.
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Applies an MBO record to the book, returning a TBBO record if the record was a
+    /// trade.
+    ///
+    /// # Errors
+    /// This function returns an error if the record can't be applied to the book. See
+    /// [`OrderBook::apply`].
+    pub fn apply(&mut self, mbo: &MboMsg) -> Result<Option<TbboMsg>> {
+        let booklevel = self.book.top_levels();
+        self.book.apply(mbo)?;
+        if mbo.action()? != Action::Trade {
+            return Ok(None);
+        }
+        Ok(Some(TbboMsg {
+            hd: header::<TbboMsg>(mbo),
+            price: mbo.price,
+            size: mbo.size,
+            action: mbo.action,
+            side: mbo.side,
+            flags: mbo.flags,
+            depth: 0,
+            ts_recv: mbo.ts_recv,
+            ts_in_delta: mbo.ts_in_delta,
+            sequence: mbo.sequence,
+            booklevel,
+        }))
+    }
+}
+
+/// Returns the index of the level at `price` on `side` of `levels`, or `None` if
+/// there's no such level or its index doesn't fit in a `u8`.
+fn level_index<const N: usize>(levels: &[BidAskPair; N], side: Side, price: Price) -> Option<u8> {
+    let position = match side {
+        Side::Bid => levels.iter().position(|l| l.bid_px == price.raw()),
+        Side::Ask => levels.iter().position(|l| l.ask_px == price.raw()),
+        Side::None => None,
+    };
+    position.and_then(|i| u8::try_from(i).ok())
+}
+
+fn header<T: ConstTypeId>(mbo: &MboMsg) -> RecordHeader {
+    RecordHeader::new::<T>(mbo.hd.publisher_id, mbo.hd.product_id, mbo.hd.ts_event)
+}
+
+macro_rules! impl_from_mbp_update {
+    ($($n:literal => $ty:ty),*) => {
+        $(
+            impl From<MbpUpdate<$n>> for $ty {
+                fn from(update: MbpUpdate<$n>) -> Self {
+                    Self {
+                        hd: RecordHeader::new::<$ty>(
+                            update.publisher_id,
+                            update.product_id,
+                            update.ts_event,
+                        ),
+                        price: update.price.raw(),
+                        size: update.size,
+                        action: c_char::from(update.action),
+                        side: c_char::from(update.side),
+                        flags: update.flags,
+                        depth: update.depth,
+                        ts_recv: update.ts_recv,
+                        ts_in_delta: update.ts_in_delta,
+                        sequence: update.sequence,
+                        booklevel: update.booklevel,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_mbp_update!(1 => Mbp1Msg, 10 => Mbp10Msg);
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use super::*;
+    use crate::{price::UNDEF_PRICE, test_utils::mbo};
+
+    fn pair(bid: Option<(i64, u32, u32)>, ask: Option<(i64, u32, u32)>) -> BidAskPair {
+        let (bid_px, bid_sz, bid_ct) = bid.unwrap_or((UNDEF_PRICE, 0, 0));
+        let (ask_px, ask_sz, ask_ct) = ask.unwrap_or((UNDEF_PRICE, 0, 0));
+        BidAskPair {
+            bid_px,
+            ask_px,
+            bid_sz,
+            ask_sz,
+            bid_ct,
+            ask_ct,
+        }
+    }
+
+    #[test]
+    fn test_mbp1() {
+        let mut builder = MbpBuilder::<1>::new();
+        let update = builder
+            .apply(&mbo(Action::Add, Side::Bid, 1, 100, 10))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.action, Action::Add);
+        assert_eq!(update.side, Side::Bid);
+        assert_eq!(update.depth, 0);
+        assert_eq!(update.booklevel, [pair(Some((100, 10, 1)), None)]);
+        // Below the top of book
+        assert!(builder
+            .apply(&mbo(Action::Add, Side::Bid, 2, 99, 10))
+            .unwrap()
+            .is_none());
+        let update = builder
+            .apply(&mbo(Action::Add, Side::Ask, 3, 101, 5))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            update.booklevel,
+            [pair(Some((100, 10, 1)), Some((101, 5, 1)))]
+        );
+        // Trades are always emitted and don't change the book
+        let update = builder
+            .apply(&mbo(Action::Trade, Side::Ask, 0, 100, 4))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.action, Action::Trade);
+        assert_eq!(update.side, Side::Ask);
+        assert_eq!(update.size, 4);
+        assert_eq!(
+            update.booklevel,
+            [pair(Some((100, 10, 1)), Some((101, 5, 1)))]
+        );
+        assert!(builder
+            .apply(&mbo(Action::Fill, Side::Bid, 1, 100, 4))
+            .unwrap()
+            .is_none());
+        let update = builder
+            .apply(&mbo(Action::Cancel, Side::Bid, 1, 100, 10))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.action, Action::Cancel);
+        assert_eq!(update.depth, 0);
+        assert_eq!(
+            update.booklevel,
+            [pair(Some((99, 10, 1)), Some((101, 5, 1)))]
+        );
+
+        let rec = Mbp1Msg::from(update);
+        assert_eq!(rec.hd.rtype, Mbp1Msg::TYPE_ID);
+        assert_eq!(rec.hd.length as usize * 4, mem::size_of::<Mbp1Msg>());
+        assert_eq!(rec.action().unwrap(), Action::Cancel);
+        assert_eq!(rec.price, 100);
+        assert_eq!(rec.ts_recv, 1658441891000000000);
+    }
+
+    #[test]
+    fn test_mbp10_depth() {
+        let mut builder = MbpBuilder::<10>::new();
+        for (id, price) in [(1, 100), (2, 99), (3, 98)] {
+            builder
+                .apply(&mbo(Action::Add, Side::Bid, id, price, 1))
+                .unwrap()
+                .unwrap();
+        }
+        let update = builder
+            .apply(&mbo(Action::Modify, Side::Bid, 3, 98, 5))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.depth, 2);
+        assert_eq!(update.booklevel[2], pair(Some((98, 5, 1)), None));
+        // The removed level is found in the previous book
+        let update = builder
+            .apply(&mbo(Action::Cancel, Side::Bid, 2, 99, 1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.depth, 1);
+        assert_eq!(update.booklevel[1], pair(Some((98, 5, 1)), None));
+        assert_eq!(update.booklevel[2], pair(None, None));
+        let update = builder
+            .apply(&mbo(Action::Clear, Side::None, 0, 0, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.action, Action::Clear);
+        assert_eq!(update.booklevel, std::array::from_fn(|_| pair(None, None)));
+        let rec = Mbp10Msg::from(update);
+        assert_eq!(rec.hd.rtype, Mbp10Msg::TYPE_ID);
+        assert_eq!(rec.hd.length as usize * 4, mem::size_of::<Mbp10Msg>());
+    }
+
+    #[test]
+    fn test_deep_book() {
+        let mut builder = MbpBuilder::<20>::new();
+        for id in 0..20 {
+            builder
+                .apply(&mbo(Action::Add, Side::Ask, id, 100 + id as i64, 1))
+                .unwrap()
+                .unwrap();
+        }
+        assert_eq!(builder.book().asks().count(), 20);
+        let update = builder
+            .apply(&mbo(Action::Cancel, Side::Ask, 19, 119, 1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.depth, 19);
+        assert_eq!(update.booklevel[19], pair(None, None));
+    }
+
+    #[test]
+    fn test_tbbo() {
+        let mut builder = TbboBuilder::new();
+        for rec in [
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::Ask, 2, 101, 5),
+        ] {
+            assert!(builder.apply(&rec).unwrap().is_none());
+        }
+        let tbbo = builder
+            .apply(&mbo(Action::Trade, Side::Bid, 0, 101, 5))
+            .unwrap()
+            .unwrap();
+        assert_eq!(tbbo.hd.rtype, TbboMsg::TYPE_ID);
+        assert_eq!(tbbo.action().unwrap(), Action::Trade);
+        assert_eq!(tbbo.side().unwrap(), Side::Bid);
+        assert_eq!(tbbo.price, 101);
+        assert_eq!(
+            tbbo.booklevel,
+            [pair(Some((100, 10, 1)), Some((101, 5, 1)))]
+        );
+        // The BBO is from before the trade's resulting cancel
+        builder
+            .apply(&mbo(Action::Cancel, Side::Ask, 2, 101, 5))
+            .unwrap();
+        let tbbo = builder
+            .apply(&mbo(Action::Trade, Side::Ask, 0, 100, 1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(tbbo.booklevel, [pair(Some((100, 10, 1)), None)]);
+    }
+
+    #[test]
+    fn test_level_index_beyond_u8() {
+        let mut levels: [BidAskPair; 300] = std::array::from_fn(|_| BidAskPair::default());
+        levels[255].bid_px = 100;
+        levels[299].ask_px = 101;
+        assert_eq!(
+            level_index(&levels, Side::Bid, Price::from_raw(100)),
+            Some(255)
+        );
+        assert_eq!(level_index(&levels, Side::Ask, Price::from_raw(101)), None);
+    }
+
+    #[test]
+    fn test_depth_beyond_u8() {
+        let mut builder = MbpBuilder::<300>::new();
+        for i in 0..256 {
+            let update = builder
+                .apply(&mbo(Action::Add, Side::Bid, i + 1, 1000 - i as i64, 1))
+                .unwrap()
+                .unwrap();
+            assert_eq!(update.depth as u64, i);
+        }
+        assert!(matches!(
+            builder.apply(&mbo(Action::Add, Side::Bid, 257, 500, 1)),
+            Err(Error::Book(_))
+        ));
+    }
+}
@@ -0,0 +1,504 @@
+//! The metadata header at the beginning of DBZ files.
+//!
+//! The variable-length part of the metadata is Zstandard-compressed, so decoding and
+//! encoding metadata requires the `zstd` feature.
+use std::{io, mem::size_of};
+
+use crate::{
+    enums::{Compression, SType, Schema},
+    Error, Result,
+};
+
+/// The current version of the DBZ metadata format.
+pub const DBZ_VERSION: u8 = 1;
+/// The fixed length of the NUL-padded dataset string.
+pub const DATASET_CSTR_LEN: usize = 16;
+/// The fixed length of the NUL-padded symbol strings.
+pub const SYMBOL_CSTR_LEN: usize = 22;
+/// The magic number of the Zstandard skippable frame containing the metadata. Using a
+/// skippable frame allows Zstandard decoders to ignore the metadata.
+pub const METADATA_MAGIC: u32 = 0x184D2A50;
+
+const DBZ_PREFIX: &[u8; 3] = b"DBZ";
+const FIXED_METADATA_LEN: usize = DBZ_PREFIX.len()
+    + size_of::<u8>() // version
+    + DATASET_CSTR_LEN
+    + size_of::<u16>() // schema
+    + 4 * size_of::<u64>() // start, end, limit, and record_count
+    + 3 * size_of::<u8>() // compression, stype_in, and stype_out
+    + RESERVED_LEN;
+/// The length of the reserved padding after the fixed fields of the metadata.
+const RESERVED_LEN: usize = 47;
+
+/// Information about the data contained in a DBZ file.
+///
+/// The encoded metadata is followed by the body of the file, which can be read with a
+/// [`RecordDecoder`](crate::RecordDecoder) once the metadata has been decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+    /// The DBZ schema version number.
+    pub version: u8,
+    /// The dataset name.
+    pub dataset: String,
+    /// The data record schema. Specifies which record type is stored in the file.
+    pub schema: Schema,
+    /// The UNIX nanosecond timestamp of the query start, or the first record if the file
+    /// was split.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::record::serialize_large_u64",
+            deserialize_with = "crate::record::deserialize_large_u64"
+        )
+    )]
+    pub start: u64,
+    /// The UNIX nanosecond timestamp of the query end, or the last record if the file
+    /// was split.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::record::serialize_large_u64",
+            deserialize_with = "crate::record::deserialize_large_u64"
+        )
+    )]
+    pub end: u64,
+    /// The maximum number of records for the query, or 0 for no limit.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::record::serialize_large_u64",
+            deserialize_with = "crate::record::deserialize_large_u64"
+        )
+    )]
+    pub limit: u64,
+    /// The total number of data records.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::record::serialize_large_u64",
+            deserialize_with = "crate::record::deserialize_large_u64"
+        )
+    )]
+    pub record_count: u64,
+    /// The data compression format of the body.
+    pub compression: Compression,
+    /// The input symbology type to map from.
+    pub stype_in: SType,
+    /// The output symbology type to map to.
+    pub stype_out: SType,
+    /// The original query input symbols from the request.
+    pub symbols: Vec<String>,
+    /// Symbols that did not resolve for _at least one day_ in the query time range.
+    pub partial: Vec<String>,
+    /// Symbols that did not resolve for _any_ day in the query time range.
+    pub not_found: Vec<String>,
+    /// Symbol mappings containing a native symbol and its mapping intervals.
+    pub mappings: Vec<SymbolMapping>,
+}
+
+/// A native symbol and its symbol mappings for different time ranges within the query
+/// range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolMapping {
+    /// The native symbol.
+    pub native: String,
+    /// The mappings of `native` for different date ranges.
+    pub intervals: Vec<MappingInterval>,
+}
+
+/// The resolved symbol for a date range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MappingInterval {
+    /// The start date of the range (inclusive) expressed as `YYYYMMDD`.
+    pub start_date: u32,
+    /// The end date of the range (exclusive) expressed as `YYYYMMDD`.
+    pub end_date: u32,
+    /// The resolved symbol for the date range.
+    pub symbol: String,
+}
+
+impl Metadata {
+    /// Decodes the metadata from the beginning of `reader`, leaving `reader`
+    /// positioned at the start of the body.
+    ///
+    /// # Errors
+    /// This function returns an error if reading fails, the metadata is malformed or
+    /// of an unsupported version, or the `zstd` feature isn't enabled.
+    pub fn decode(reader: &mut impl io::Read) -> Result<Self> {
+        let mut prelude = [0; 2 * size_of::<u32>()];
+        reader.read_exact(&mut prelude).map_err(eof_to_decode_err)?;
+        let magic = u32::from_le_bytes(prelude[..4].try_into().unwrap());
+        if magic != METADATA_MAGIC {
+            return Err(Error::Decode(format!(
+                "Invalid metadata magic number {magic:#010X}"
+            )));
+        }
+        let frame_size = u32::from_le_bytes(prelude[4..].try_into().unwrap()) as usize;
+        if frame_size < FIXED_METADATA_LEN {
+            return Err(Error::Decode(format!(
+                "Metadata frame of {frame_size} bytes is shorter than the minimum of {FIXED_METADATA_LEN} bytes"
+            )));
+        }
+        let mut frame = vec![0; frame_size];
+        reader.read_exact(&mut frame).map_err(eof_to_decode_err)?;
+        Self::decode_frame(&frame)
+    }
+
+    fn decode_frame(frame: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor(frame);
+        if cursor.take(DBZ_PREFIX.len())? != DBZ_PREFIX {
+            return Err(Error::Decode("Missing DBZ prefix in metadata".to_owned()));
+        }
+        let version = cursor.u8()?;
+        if version > DBZ_VERSION {
+            return Err(Error::Decode(format!(
+                "Unsupported DBZ version {version}, the latest supported version is {DBZ_VERSION}"
+            )));
+        }
+        let dataset = cursor.cstr(DATASET_CSTR_LEN)?;
+        let schema = Schema::try_from(cursor.u16()?)
+            .map_err(|e| Error::Decode(format!("Invalid schema: {e}")))?;
+        let start = cursor.u64()?;
+        let end = cursor.u64()?;
+        let limit = cursor.u64()?;
+        let record_count = cursor.u64()?;
+        let compression = Compression::try_from(cursor.u8()?)
+            .map_err(|e| Error::Decode(format!("Invalid compression: {e}")))?;
+        let stype_in = SType::try_from(cursor.u8()?)
+            .map_err(|e| Error::Decode(format!("Invalid stype_in: {e}")))?;
+        let stype_out = SType::try_from(cursor.u8()?)
+            .map_err(|e| Error::Decode(format!("Invalid stype_out: {e}")))?;
+        cursor.take(RESERVED_LEN)?;
+        let var_buffer = decompress(cursor.0)?;
+        let mut cursor = Cursor(&var_buffer);
+        let schema_definition_length = cursor.u32()?;
+        if schema_definition_length != 0 {
+            return Err(Error::Decode(
+                "Decoding metadata with a schema definition is not supported".to_owned(),
+            ));
+        }
+        let symbols = cursor.symbols()?;
+        let partial = cursor.symbols()?;
+        let not_found = cursor.symbols()?;
+        let mapping_count = cursor.u32()?;
+        let mut mappings = Vec::with_capacity(mapping_count.min(1024) as usize);
+        for _ in 0..mapping_count {
+            let native = cursor.cstr(SYMBOL_CSTR_LEN)?;
+            let interval_count = cursor.u32()?;
+            let mut intervals = Vec::with_capacity(interval_count.min(1024) as usize);
+            for _ in 0..interval_count {
+                intervals.push(MappingInterval {
+                    start_date: cursor.u32()?,
+                    end_date: cursor.u32()?,
+                    symbol: cursor.cstr(SYMBOL_CSTR_LEN)?,
+                });
+            }
+            mappings.push(SymbolMapping { native, intervals });
+        }
+        Ok(Self {
+            version,
+            dataset,
+            schema,
+            start,
+            end,
+            limit,
+            record_count,
+            compression,
+            stype_in,
+            stype_out,
+            symbols,
+            partial,
+            not_found,
+            mappings,
+        })
+    }
+
+    /// Encodes the metadata to `writer`. The body should be written immediately
+    /// afterwards.
+    ///
+    /// # Errors
+    /// This function returns an error if a string is too long for its fixed-length
+    /// field, there are too many symbols, the `zstd` feature isn't enabled, or writing
+    /// fails.
+    pub fn encode(&self, writer: &mut impl io::Write) -> Result<()> {
+        let mut frame = Vec::with_capacity(FIXED_METADATA_LEN);
+        frame.extend_from_slice(DBZ_PREFIX);
+        frame.push(self.version);
+        encode_cstr(&mut frame, &self.dataset, DATASET_CSTR_LEN)?;
+        frame.extend_from_slice(&(self.schema as u16).to_le_bytes());
+        for num in [self.start, self.end, self.limit, self.record_count] {
+            frame.extend_from_slice(&num.to_le_bytes());
+        }
+        frame.push(self.compression as u8);
+        frame.push(self.stype_in as u8);
+        frame.push(self.stype_out as u8);
+        frame.resize(FIXED_METADATA_LEN, 0);
+
+        // schema_definition_length
+        let mut var_buffer = 0_u32.to_le_bytes().to_vec();
+        for symbols in [&self.symbols, &self.partial, &self.not_found] {
+            encode_len(&mut var_buffer, symbols.len())?;
+            for symbol in symbols {
+                encode_cstr(&mut var_buffer, symbol, SYMBOL_CSTR_LEN)?;
+            }
+        }
+        encode_len(&mut var_buffer, self.mappings.len())?;
+        for mapping in &self.mappings {
+            encode_cstr(&mut var_buffer, &mapping.native, SYMBOL_CSTR_LEN)?;
+            encode_len(&mut var_buffer, mapping.intervals.len())?;
+            for interval in &mapping.intervals {
+                var_buffer.extend_from_slice(&interval.start_date.to_le_bytes());
+                var_buffer.extend_from_slice(&interval.end_date.to_le_bytes());
+                encode_cstr(&mut var_buffer, &interval.symbol, SYMBOL_CSTR_LEN)?;
+            }
+        }
+        frame.extend_from_slice(&compress(&var_buffer)?);
+        let frame_size = u32::try_from(frame.len())
+            .map_err(|_| Error::Encode("Metadata is too large to encode".to_owned()))?;
+        writer.write_all(&METADATA_MAGIC.to_le_bytes())?;
+        writer.write_all(&frame_size.to_le_bytes())?;
+        writer.write_all(&frame)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zstd")]
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+    zstd::stream::decode_all(bytes)
+        .map_err(|e| Error::Decode(format!("Failed to decompress metadata: {e}")))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress(_bytes: &[u8]) -> Result<Vec<u8>> {
+    Err(Error::Decode(
+        "Decoding metadata requires the `zstd` feature".to_owned(),
+    ))
+}
+
+#[cfg(feature = "zstd")]
+fn compress(bytes: &[u8]) -> Result<Vec<u8>> {
+    zstd::stream::encode_all(bytes, zstd::DEFAULT_COMPRESSION_LEVEL)
+        .map_err(|e| Error::Encode(format!("Failed to compress metadata: {e}")))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn compress(_bytes: &[u8]) -> Result<Vec<u8>> {
+    Err(Error::Encode(
+        "Encoding metadata requires the `zstd` feature".to_owned(),
+    ))
+}
+
+fn eof_to_decode_err(err: io::Error) -> Error {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+        Error::Decode("Unexpected end of stream while reading metadata".to_owned())
+    } else {
+        Error::Io(err)
+    }
+}
+
+fn encode_len(frame: &mut Vec<u8>, len: usize) -> Result<()> {
+    let len = u32::try_from(len)
+        .map_err(|_| Error::Encode(format!("Too many metadata entries: {len}")))?;
+    frame.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+fn encode_cstr(frame: &mut Vec<u8>, s: &str, len: usize) -> Result<()> {
+    // leave room for the NUL terminator
+    if s.len() >= len {
+        return Err(Error::Encode(format!(
+            "'{s}' is too long for a field of {len} bytes"
+        )));
+    }
+    frame.extend_from_slice(s.as_bytes());
+    frame.resize(frame.len() + len - s.len(), 0);
+    Ok(())
+}
+
+/// Reads little-endian values from a byte slice.
+struct Cursor<'a>(&'a [u8]);
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::Decode("Unexpected end of metadata frame".to_owned()));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        self.take_array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.take_array().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        self.take_array().map(u64::from_le_bytes)
+    }
+
+    fn cstr(&mut self, len: usize) -> Result<String> {
+        let bytes = self.take(len)?;
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..end])
+            .map(str::to_owned)
+            .map_err(|e| Error::Decode(format!("Invalid UTF-8 in metadata string: {e}")))
+    }
+
+    fn symbols(&mut self) -> Result<Vec<String>> {
+        let count = self.u32()?;
+        (0..count).map(|_| self.cstr(SYMBOL_CSTR_LEN)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> Metadata {
+        Metadata {
+            version: DBZ_VERSION,
+            dataset: "GLBX.MDP3".to_owned(),
+            schema: Schema::Mbo,
+            start: 1658441851000000000,
+            end: 1658451851000000000,
+            limit: 0,
+            record_count: 2,
+            compression: Compression::ZStd,
+            stype_in: SType::Native,
+            stype_out: SType::ProductId,
+            symbols: vec!["ESU2".to_owned(), "ESZ2".to_owned()],
+            partial: vec!["ESZ2".to_owned()],
+            not_found: vec![],
+            mappings: vec![SymbolMapping {
+                native: "ESU2".to_owned(),
+                intervals: vec![
+                    MappingInterval {
+                        start_date: 20220721,
+                        end_date: 20220722,
+                        symbol: "5482".to_owned(),
+                    },
+                    MappingInterval {
+                        start_date: 20220722,
+                        end_date: 20220723,
+                        symbol: "5483".to_owned(),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let metadata = metadata();
+        let mut buffer = Vec::new();
+        metadata.encode(&mut buffer).unwrap();
+        // body
+        buffer.extend_from_slice(&[1, 2, 3]);
+        let mut reader = buffer.as_slice();
+        assert_eq!(Metadata::decode(&mut reader).unwrap(), metadata);
+        assert_eq!(reader, &[1, 2, 3]);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_decode_invalid_magic() {
+        let mut buffer = Vec::new();
+        metadata().encode(&mut buffer).unwrap();
+        buffer[0] = 0;
+        assert!(matches!(
+            Metadata::decode(&mut buffer.as_slice()),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_decode_truncated() {
+        let mut buffer = Vec::new();
+        metadata().encode(&mut buffer).unwrap();
+        buffer.pop();
+        assert!(matches!(
+            Metadata::decode(&mut buffer.as_slice()),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_decode_dbz_file() {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/test_data.mbo.dbz"
+        ))
+        .unwrap();
+        let mut reader = bytes.as_slice();
+        let metadata = Metadata::decode(&mut reader).unwrap();
+        assert_eq!(metadata.version, 1);
+        assert_eq!(metadata.dataset, "GLBX.MDP3");
+        assert_eq!(metadata.schema, Schema::Mbo);
+        assert_eq!(metadata.start, 1609160400000000000);
+        assert_eq!(metadata.end, 1609200000000000000);
+        assert_eq!(metadata.limit, 2);
+        assert_eq!(metadata.record_count, 2);
+        assert_eq!(metadata.compression, Compression::ZStd);
+        assert_eq!(metadata.stype_in, SType::Native);
+        assert_eq!(metadata.stype_out, SType::ProductId);
+        assert_eq!(metadata.symbols, vec!["ESH1".to_owned()]);
+        assert!(metadata.partial.is_empty());
+        assert!(metadata.not_found.is_empty());
+        assert_eq!(
+            metadata.mappings,
+            vec![SymbolMapping {
+                native: "ESH1".to_owned(),
+                intervals: vec![MappingInterval {
+                    start_date: 20201228,
+                    end_date: 20201229,
+                    symbol: "5482".to_owned(),
+                }],
+            }]
+        );
+        let body = crate::compression::DynReader::new(reader, metadata.compression).unwrap();
+        let records = crate::RecordDecoder::new(body)
+            .decode_records::<crate::record::MboMsg>()
+            .unwrap();
+        assert_eq!(records.len() as u64, metadata.record_count);
+        assert_eq!(records[0].hd.product_id, 5482);
+        assert_eq!(records[0].price, 3722750000000);
+        assert_eq!(records[1].order_id, 647784973631);
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_zstd_unsupported() {
+        assert!(matches!(
+            metadata().encode(&mut Vec::new()),
+            Err(Error::Encode(_))
+        ));
+    }
+
+    #[test]
+    fn test_encode_symbol_too_long() {
+        let mut metadata = metadata();
+        metadata.symbols.push("A".repeat(SYMBOL_CSTR_LEN));
+        assert!(matches!(
+            metadata.encode(&mut Vec::new()),
+            Err(Error::Encode(_))
+        ));
+    }
+}
@@ -0,0 +1,512 @@
+//! Aggregation of trades into open, high, low, close, and volume (OHLCV) bars.
+use std::collections::HashMap;
+
+use crate::{
+    enums::{Action, Schema},
+    record::{MboMsg, Mbp1Msg, OhlcvMsg, RecordHeader, TradeMsg},
+    Duration, Error, Price, Result,
+};
+
+/// How to handle intervals without any trades for an instrument.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EmptyIntervals {
+    /// Don't emit bars for intervals without trades.
+    #[default]
+    Skip,
+    /// Emit a bar with zero volume and the previous bar's close as its open, high,
+    /// low, and close for each interval without trades between two bars.
+    CarryForward,
+}
+
+/// Aggregates trades into [`OhlcvMsg`] bars of a fixed interval for each combination
+/// of [`publisher_id`](RecordHeader::publisher_id) and
+/// [`product_id`](RecordHeader::product_id).
+///
+/// Bars are aligned to multiples of the interval since the UNIX epoch and the
+/// `ts_event` of each bar is the start of its interval, as in the OHLCV schemas. A bar
+/// is emitted once a trade for the same instrument arrives in a later interval, or
+/// when [`OhlcvAggregator::flush`] is called. Trades with a `ts_event` before the
+/// current bar's interval update the high, low, and volume of the current bar, but
+/// never its open or close. Trades with an undefined price or `ts_event` are ignored.
+#[derive(Clone, Debug)]
+pub struct OhlcvAggregator {
+    interval: u64,
+    empty_intervals: EmptyIntervals,
+    bars: HashMap<(u16, u32), Bar>,
+}
+
+impl OhlcvAggregator {
+    /// Creates a new aggregator for bars of `interval`.
+    ///
+    /// # Errors
+    /// This function returns an error if `interval` isn't positive.
+    pub fn new(interval: Duration, empty_intervals: EmptyIntervals) -> Result<Self> {
+        match u64::try_from(interval.nanos()) {
+            Ok(interval) if interval > 0 => Ok(Self {
+                interval,
+                empty_intervals,
+                bars: HashMap::new(),
+            }),
+            _ => Err(Error::TypeConversion("OHLCV interval must be positive")),
+        }
+    }
+
+    /// Creates a new aggregator for the bar interval of an OHLCV `schema`.
+    ///
+    /// # Errors
+    /// This function returns an error if `schema` isn't an OHLCV schema.
+    pub fn for_schema(schema: Schema, empty_intervals: EmptyIntervals) -> Result<Self> {
+        let interval = schema
+            .ohlcv_interval()
+            .ok_or(Error::TypeConversion("Schema isn't an OHLCV schema"))?;
+        Self::new(interval, empty_intervals)
+    }
+
+    /// Returns the bar interval.
+    pub fn interval(&self) -> Duration {
+        Duration::from_nanos(self.interval as i64)
+    }
+
+    /// Aggregates a trade, returning any bars completed by it.
+    pub fn apply_trade(&mut self, trade: &TradeMsg) -> Vec<OhlcvMsg> {
+        self.update(&trade.hd, trade.price(), trade.size)
+    }
+
+    /// Aggregates an MBO record if it's a trade, returning any bars completed by it.
+    pub fn apply_mbo(&mut self, mbo: &MboMsg) -> Vec<OhlcvMsg> {
+        if mbo.action().ok() == Some(Action::Trade) {
+            self.update(&mbo.hd, mbo.price(), mbo.size)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Aggregates an MBP-1 record if it's a trade, returning any bars completed by it.
+    pub fn apply_mbp1(&mut self, mbp1: &Mbp1Msg) -> Vec<OhlcvMsg> {
+        if mbp1.action().ok() == Some(Action::Trade) {
+            self.update(&mbp1.hd, mbp1.price(), mbp1.size)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Returns the bars in progress for all instruments, ordered by `ts_event`,
+    /// `publisher_id`, and `product_id`, and resets the aggregator.
+    pub fn flush(&mut self) -> Vec<OhlcvMsg> {
+        flush(&mut self.bars)
+    }
+
+    fn update(&mut self, hd: &RecordHeader, price: Price, size: u32) -> Vec<OhlcvMsg> {
+        if price.is_undefined() || hd.ts_event().is_undefined() {
+            return Vec::new();
+        }
+        let trade = Bar {
+            start: hd.ts_event - hd.ts_event % self.interval,
+            open: price.raw(),
+            high: price.raw(),
+            low: price.raw(),
+            close: price.raw(),
+            volume: size as u64,
+        };
+        aggregate(
+            &mut self.bars,
+            (hd.publisher_id, hd.product_id),
+            trade,
+            self.interval,
+            self.empty_intervals,
+        )
+    }
+}
+
+/// Resamples [`OhlcvMsg`] bars into bars of a coarser interval for each combination
+/// of [`publisher_id`](RecordHeader::publisher_id) and
+/// [`product_id`](RecordHeader::product_id).
+///
+/// The open of each resampled bar is the open of its first source bar, the close is
+/// the close of its last source bar, the high and low are the extremes across the
+/// source bars, and the volume is their sum. As with [`OhlcvAggregator`], a bar is
+/// emitted once a source bar for the same instrument arrives in a later interval, or
+/// when [`OhlcvResampler::flush`] is called. Source bars from before the current
+/// bar's interval update the high, low, and volume of the current bar, but never its
+/// open or close. Intervals without any source bars are skipped and source bars with an
+/// undefined `ts_event` are ignored.
+#[derive(Clone, Debug)]
+pub struct OhlcvResampler {
+    interval: u64,
+    bars: HashMap<(u16, u32), Bar>,
+}
+
+impl OhlcvResampler {
+    /// Creates a new resampler from bars of the `source` OHLCV schema to bars of
+    /// `interval`.
+    ///
+    /// # Errors
+    /// This function returns an error if `source` isn't an OHLCV schema or if
+    /// `interval` isn't a positive multiple of the interval of `source`.
+    pub fn new(source: Schema, interval: Duration) -> Result<Self> {
+        let source_interval = source
+            .ohlcv_interval()
+            .ok_or(Error::TypeConversion("Schema isn't an OHLCV schema"))?;
+        if interval.nanos() <= 0 || interval.nanos() % source_interval.nanos() != 0 {
+            return Err(Error::TypeConversion(
+                "OHLCV interval must be a positive multiple of the source interval",
+            ));
+        }
+        Ok(Self {
+            interval: interval.nanos() as u64,
+            bars: HashMap::new(),
+        })
+    }
+
+    /// Creates a new resampler from bars of the `source` OHLCV schema to bars of the
+    /// `target` OHLCV schema.
+    ///
+    /// # Errors
+    /// This function returns an error if either schema isn't an OHLCV schema or if
+    /// `target` has a shorter interval than `source`.
+    pub fn for_schemas(source: Schema, target: Schema) -> Result<Self> {
+        let interval = target
+            .ohlcv_interval()
+            .ok_or(Error::TypeConversion("Schema isn't an OHLCV schema"))?;
+        Self::new(source, interval)
+    }
+
+    /// Returns the interval of the resampled bars.
+    pub fn interval(&self) -> Duration {
+        Duration::from_nanos(self.interval as i64)
+    }
+
+    /// Resamples a bar, returning any bars completed by it.
+    pub fn apply(&mut self, ohlcv: &OhlcvMsg) -> Vec<OhlcvMsg> {
+        if ohlcv.hd.ts_event().is_undefined() {
+            return Vec::new();
+        }
+        let bar = Bar {
+            start: ohlcv.hd.ts_event - ohlcv.hd.ts_event % self.interval,
+            open: ohlcv.open,
+            high: ohlcv.high,
+            low: ohlcv.low,
+            close: ohlcv.close,
+            volume: ohlcv.volume,
+        };
+        aggregate(
+            &mut self.bars,
+            (ohlcv.hd.publisher_id, ohlcv.hd.product_id),
+            bar,
+            self.interval,
+            EmptyIntervals::Skip,
+        )
+    }
+
+    /// Returns the bars in progress for all instruments, ordered by `ts_event`,
+    /// `publisher_id`, and `product_id`, and resets the resampler.
+    pub fn flush(&mut self) -> Vec<OhlcvMsg> {
+        flush(&mut self.bars)
+    }
+}
+
+/// Merges `update`, whose `start` is already aligned to `interval`, into the bar in
+/// progress for `key`, returning any completed bars.
+fn aggregate(
+    bars: &mut HashMap<(u16, u32), Bar>,
+    key: (u16, u32),
+    update: Bar,
+    interval: u64,
+    empty_intervals: EmptyIntervals,
+) -> Vec<OhlcvMsg> {
+    let Some(bar) = bars.get_mut(&key) else {
+        bars.insert(key, update);
+        return Vec::new();
+    };
+    if update.start == bar.start {
+        bar.merge(&update);
+        return Vec::new();
+    }
+    if update.start < bar.start {
+        bar.merge_late(&update);
+        return Vec::new();
+    }
+    let mut completed = vec![bar.to_record(key)];
+    if empty_intervals == EmptyIntervals::CarryForward {
+        let mut empty_start = bar.start + interval;
+        while empty_start < update.start {
+            completed.push(Bar::empty(empty_start, bar.close).to_record(key));
+            empty_start += interval;
+        }
+    }
+    *bar = update;
+    completed
+}
+
+fn flush(bars: &mut HashMap<(u16, u32), Bar>) -> Vec<OhlcvMsg> {
+    let mut bars = bars
+        .drain()
+        .map(|(key, bar)| bar.to_record(key))
+        .collect::<Vec<_>>();
+    bars.sort_unstable_by_key(|bar| (bar.hd.ts_event, bar.hd.publisher_id, bar.hd.product_id));
+    bars
+}
+
+/// A bar in progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Bar {
+    start: u64,
+    open: i64,
+    high: i64,
+    low: i64,
+    close: i64,
+    volume: u64,
+}
+
+impl Bar {
+    fn empty(start: u64, close: i64) -> Self {
+        Self {
+            start,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0,
+        }
+    }
+
+    /// Merges a later bar into this bar.
+    fn merge(&mut self, later: &Bar) {
+        self.high = self.high.max(later.high);
+        self.low = self.low.min(later.low);
+        self.close = later.close;
+        self.volume = self.volume.saturating_add(later.volume);
+    }
+
+    /// Merges a bar from an earlier interval into this bar. Its prices are out of
+    /// sequence, so they can extend the range of this bar but don't change its open
+    /// or close.
+    fn merge_late(&mut self, earlier: &Bar) {
+        self.high = self.high.max(earlier.high);
+        self.low = self.low.min(earlier.low);
+        self.volume = self.volume.saturating_add(earlier.volume);
+    }
+
+    fn to_record(self, (publisher_id, product_id): (u16, u32)) -> OhlcvMsg {
+        OhlcvMsg {
+            hd: RecordHeader::new::<OhlcvMsg>(publisher_id, product_id, self.start),
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::raw::c_char;
+
+    use super::*;
+    use crate::{enums::Side, timestamp::UNDEF_TIMESTAMP};
+
+    const SEC: u64 = 1_000_000_000;
+    const START: u64 = 1658441820 * SEC;
+
+    fn trade(product_id: u32, ts_event: u64, price: i64, size: u32) -> TradeMsg {
+        TradeMsg {
+            hd: RecordHeader::new::<TradeMsg>(1, product_id, ts_event),
+            price,
+            size,
+            action: c_char::from(Action::Trade),
+            side: c_char::from(Side::Ask),
+            flags: 0,
+            depth: 0,
+            ts_recv: ts_event + 1000,
+            ts_in_delta: 0,
+            sequence: 0,
+            booklevel: [],
+        }
+    }
+
+    fn bar(product_id: u32, ts_event: u64, ohlc: [i64; 4], volume: u64) -> OhlcvMsg {
+        OhlcvMsg {
+            hd: RecordHeader::new::<OhlcvMsg>(1, product_id, ts_event),
+            open: ohlc[0],
+            high: ohlc[1],
+            low: ohlc[2],
+            close: ohlc[3],
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut agg = OhlcvAggregator::for_schema(Schema::Ohlcv1M, EmptyIntervals::Skip).unwrap();
+        assert!(agg.apply_trade(&trade(1, START + SEC, 100, 1)).is_empty());
+        assert!(agg
+            .apply_trade(&trade(2, START + 2 * SEC, 50, 3))
+            .is_empty());
+        assert!(agg
+            .apply_trade(&trade(1, START + 10 * SEC, 103, 2))
+            .is_empty());
+        assert!(agg
+            .apply_trade(&trade(1, START + 20 * SEC, 99, 4))
+            .is_empty());
+        assert!(agg
+            .apply_trade(&trade(1, START + 59 * SEC, 101, 1))
+            .is_empty());
+        // Late trades don't change the open or close of the current bar
+        assert!(agg.apply_trade(&trade(1, START - SEC, 98, 1)).is_empty());
+        assert_eq!(
+            agg.apply_trade(&trade(1, START + 60 * SEC, 102, 5)),
+            vec![bar(1, START, [100, 103, 98, 101], 9)]
+        );
+        assert_eq!(
+            agg.flush(),
+            vec![
+                bar(2, START, [50, 50, 50, 50], 3),
+                bar(1, START + 60 * SEC, [102, 102, 102, 102], 5)
+            ]
+        );
+        assert!(agg.flush().is_empty());
+    }
+
+    #[test]
+    fn test_carry_forward() {
+        let mut agg = OhlcvAggregator::new(
+            Duration::from_nanos(SEC as i64),
+            EmptyIntervals::CarryForward,
+        )
+        .unwrap();
+        agg.apply_trade(&trade(1, START, 100, 1));
+        let undef_trade = TradeMsg {
+            hd: RecordHeader::new::<TradeMsg>(1, 1, UNDEF_TIMESTAMP),
+            ..trade(1, START, 101, 1)
+        };
+        assert!(agg.apply_trade(&undef_trade).is_empty());
+        assert_eq!(
+            agg.apply_trade(&trade(1, START + 3 * SEC + 1, 105, 2)),
+            vec![
+                bar(1, START, [100; 4], 1),
+                bar(1, START + SEC, [100; 4], 0),
+                bar(1, START + 2 * SEC, [100; 4], 0),
+            ]
+        );
+        assert_eq!(agg.flush(), vec![bar(1, START + 3 * SEC, [105; 4], 2)]);
+    }
+
+    #[test]
+    fn test_mbo_and_mbp1() {
+        let mut agg = OhlcvAggregator::for_schema(Schema::Ohlcv1S, EmptyIntervals::Skip).unwrap();
+        let trade = trade(1, START, 100, 2);
+        let mut mbo = MboMsg {
+            hd: RecordHeader::new::<MboMsg>(
+                trade.hd.publisher_id,
+                trade.hd.product_id,
+                trade.hd.ts_event,
+            ),
+            order_id: 1,
+            price: 100,
+            size: 2,
+            flags: 0,
+            channel_id: 0,
+            action: c_char::from(Action::Add),
+            side: c_char::from(Side::Bid),
+            ts_recv: START,
+            ts_in_delta: 0,
+            sequence: 0,
+        };
+        agg.apply_mbo(&mbo);
+        mbo.action = c_char::from(Action::Trade);
+        agg.apply_mbo(&mbo);
+        agg.apply_mbp1(&Mbp1Msg {
+            hd: RecordHeader::new::<Mbp1Msg>(
+                trade.hd.publisher_id,
+                trade.hd.product_id,
+                trade.hd.ts_event,
+            ),
+            price: 101,
+            size: trade.size,
+            action: trade.action,
+            side: trade.side,
+            flags: trade.flags,
+            depth: trade.depth,
+            ts_recv: trade.ts_recv,
+            ts_in_delta: trade.ts_in_delta,
+            sequence: trade.sequence,
+            booklevel: [crate::record::BidAskPair {
+                bid_px: 100,
+                ask_px: 101,
+                bid_sz: 1,
+                ask_sz: 1,
+                bid_ct: 1,
+                ask_ct: 1,
+            }],
+        });
+        assert_eq!(agg.flush(), vec![bar(1, START, [100, 101, 100, 101], 4)]);
+    }
+
+    #[test]
+    fn test_resample() {
+        let mut resampler =
+            OhlcvResampler::new(Schema::Ohlcv1S, Duration::from_nanos(5 * SEC as i64)).unwrap();
+        assert!(resampler
+            .apply(&bar(1, START, [100, 104, 99, 101], 3))
+            .is_empty());
+        assert!(resampler
+            .apply(&bar(2, START, [50, 50, 50, 50], 1))
+            .is_empty());
+        assert!(resampler
+            .apply(&bar(1, START + 2 * SEC, [101, 102, 97, 98], 2))
+            .is_empty());
+        assert!(resampler
+            .apply(&bar(1, START + 4 * SEC, [98, 103, 98, 102], 4))
+            .is_empty());
+        // Late bars don't change the open or close of the current bar
+        assert!(resampler
+            .apply(&bar(1, START - SEC, [96, 105, 96, 96], 1))
+            .is_empty());
+        assert_eq!(
+            resampler.apply(&bar(1, START + 11 * SEC, [102, 102, 101, 101], 1)),
+            vec![bar(1, START, [100, 105, 96, 102], 10)]
+        );
+        assert_eq!(
+            resampler.flush(),
+            vec![
+                bar(2, START, [50; 4], 1),
+                bar(1, START + 10 * SEC, [102, 102, 101, 101], 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resample_saturating_volume() {
+        let mut resampler = OhlcvResampler::for_schemas(Schema::Ohlcv1S, Schema::Ohlcv1M).unwrap();
+        resampler.apply(&bar(1, START, [100; 4], u64::MAX));
+        resampler.apply(&bar(1, START + SEC, [100; 4], 1));
+        resampler.apply(&bar(1, START - SEC, [100; 4], 1));
+        assert!(resampler
+            .apply(&bar(1, UNDEF_TIMESTAMP, [100; 4], 1))
+            .is_empty());
+        assert_eq!(resampler.flush(), vec![bar(1, START, [100; 4], u64::MAX)]);
+    }
+
+    #[test]
+    fn test_resample_schemas() {
+        let resampler = OhlcvResampler::for_schemas(Schema::Ohlcv1M, Schema::Ohlcv1D).unwrap();
+        assert_eq!(
+            resampler.interval(),
+            Schema::Ohlcv1D.ohlcv_interval().unwrap()
+        );
+        assert!(OhlcvResampler::for_schemas(Schema::Ohlcv1H, Schema::Ohlcv1M).is_err());
+        assert!(OhlcvResampler::for_schemas(Schema::Trades, Schema::Ohlcv1M).is_err());
+        assert!(OhlcvResampler::for_schemas(Schema::Ohlcv1S, Schema::Mbo).is_err());
+        assert!(
+            OhlcvResampler::new(Schema::Ohlcv1M, Duration::from_nanos(90 * SEC as i64)).is_err()
+        );
+        assert!(OhlcvResampler::new(Schema::Ohlcv1M, Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_invalid_interval() {
+        assert!(OhlcvAggregator::new(Duration::ZERO, EmptyIntervals::Skip).is_err());
+        assert!(OhlcvAggregator::for_schema(Schema::Trades, EmptyIntervals::Skip).is_err());
+    }
+}
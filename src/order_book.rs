@@ -0,0 +1,434 @@
+//! A limit order book rebuilt from a stream of [`MboMsg`] records.
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    enums::{Action, Side},
+    price::UNDEF_PRICE,
+    record::{BidAskPair, MboMsg},
+    Error, Price, Result,
+};
+
+/// A resting order in an [`OrderBook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Order {
+    /// The side of the order, either [`Side::Bid`] or [`Side::Ask`].
+    pub side: Side,
+    /// The limit price of the order.
+    pub price: Price,
+    /// The remaining quantity of the order.
+    pub size: u32,
+}
+
+/// The aggregated resting orders at a single price on one side of an [`OrderBook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceLevel {
+    /// The price of the level.
+    pub price: Price,
+    /// The total quantity of all orders at the level.
+    pub size: u32,
+    /// The number of orders at the level.
+    pub count: u32,
+}
+
+/// A limit order book for a single instrument that's built by applying [`MboMsg`]
+/// records in order.
+///
+/// Orders are keyed by `order_id` and aggregated into [`PriceLevel`]s. Only
+/// [`Action::Add`], [`Action::Modify`], [`Action::Cancel`], and [`Action::Clear`]
+/// change the book: trades and fills are followed by separate cancel or modify
+/// records for the resting order, and status and update records carry no book
+/// changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OrderBook {
+    orders: HashMap<u64, Order>,
+    bids: BTreeMap<i64, PriceLevel>,
+    asks: BTreeMap<i64, PriceLevel>,
+}
+
+impl OrderBook {
+    /// Creates a new empty order book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies an MBO record to the book.
+    ///
+    /// An [`Action::Modify`] for an unknown `order_id` is treated as an
+    /// [`Action::Add`]. An [`Action::Cancel`] with a `size` less than the resting
+    /// order's is a partial cancel.
+    ///
+    /// # Errors
+    /// This function returns an error if the record's `action` or `side` is invalid,
+    /// an added order's `order_id` is already in the book, a canceled order isn't in
+    /// the book or is smaller than the canceled size, or the total size of a level
+    /// overflows. The book is unchanged when an error is returned.
+    pub fn apply(&mut self, mbo: &MboMsg) -> Result<()> {
+        match mbo.action()? {
+            Action::Add => {
+                if self.orders.contains_key(&mbo.order_id) {
+                    return Err(Error::Book(format!(
+                        "Received add for existing order ID {}",
+                        mbo.order_id
+                    )));
+                }
+                self.add(mbo.order_id, order_from(mbo)?)
+            }
+            Action::Modify => {
+                let order = order_from(mbo)?;
+                match self.orders.get(&mbo.order_id).copied() {
+                    Some(prev) if prev.side == order.side && prev.price == order.price => {
+                        let level = self.level_mut(prev.side, prev.price)?;
+                        level.size = (level.size - prev.size)
+                            .checked_add(order.size)
+                            .ok_or_else(level_size_overflow)?;
+                        self.orders.insert(mbo.order_id, order);
+                        Ok(())
+                    }
+                    Some(prev) => {
+                        // Check the new level can fit the order before removing it from
+                        // the old level so the book is unchanged on error
+                        if let Some(level) = self.levels(order.side).get(&order.price.raw()) {
+                            level
+                                .size
+                                .checked_add(order.size)
+                                .ok_or_else(level_size_overflow)?;
+                        }
+                        self.remove(mbo.order_id, prev, prev.size)?;
+                        self.add(mbo.order_id, order)
+                    }
+                    None => self.add(mbo.order_id, order),
+                }
+            }
+            Action::Cancel => {
+                let order = self.orders.get(&mbo.order_id).copied().ok_or_else(|| {
+                    Error::Book(format!(
+                        "Received cancel for unknown order ID {}",
+                        mbo.order_id
+                    ))
+                })?;
+                if mbo.size > order.size {
+                    return Err(Error::Book(format!(
+                        "Received cancel of {} for order ID {} with only {} remaining",
+                        mbo.size, mbo.order_id, order.size
+                    )));
+                }
+                self.remove(mbo.order_id, order, mbo.size)
+            }
+            Action::Clear => {
+                self.clear();
+                Ok(())
+            }
+            Action::Trade | Action::Fill | Action::Status | Action::Update => Ok(()),
+        }
+    }
+
+    /// Removes all orders from the book.
+    pub fn clear(&mut self) {
+        self.orders.clear();
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Returns `true` if the book contains no orders.
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Returns the resting order with `order_id`, if any.
+    pub fn order(&self, order_id: u64) -> Option<&Order> {
+        self.orders.get(&order_id)
+    }
+
+    /// Returns the level at `price` on `side`, if any orders rest there.
+    pub fn level(&self, side: Side, price: Price) -> Option<PriceLevel> {
+        match side {
+            Side::Bid | Side::Ask => self.levels(side).get(&price.raw()).copied(),
+            Side::None => None,
+        }
+    }
+
+    /// Returns the highest bid level, if any.
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.values().next_back().copied()
+    }
+
+    /// Returns the lowest ask level, if any.
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.values().next().copied()
+    }
+
+    /// Returns the best bid and offer.
+    pub fn bbo(&self) -> (Option<PriceLevel>, Option<PriceLevel>) {
+        (self.best_bid(), self.best_ask())
+    }
+
+    /// Returns an iterator over the bid levels from highest to lowest price.
+    pub fn bids(&self) -> impl Iterator<Item = &PriceLevel> {
+        self.bids.values().rev()
+    }
+
+    /// Returns an iterator over the ask levels from lowest to highest price.
+    pub fn asks(&self) -> impl Iterator<Item = &PriceLevel> {
+        self.asks.values()
+    }
+
+    /// Returns the top `N` levels of each side as [`BidAskPair`]s, in the same layout
+    /// as [`Mbp10Msg::booklevel`](crate::record::Mbp10Msg::booklevel). Levels beyond
+    /// the depth of a side have a price of [`UNDEF_PRICE`] and zero size and count.
+    pub fn top_levels<const N: usize>(&self) -> [BidAskPair; N] {
+        let mut bids = self.bids();
+        let mut asks = self.asks();
+        std::array::from_fn(|_| {
+            let bid = bids.next();
+            let ask = asks.next();
+            BidAskPair {
+                bid_px: bid.map_or(UNDEF_PRICE, |l| l.price.raw()),
+                ask_px: ask.map_or(UNDEF_PRICE, |l| l.price.raw()),
+                bid_sz: bid.map_or(0, |l| l.size),
+                ask_sz: ask.map_or(0, |l| l.size),
+                bid_ct: bid.map_or(0, |l| l.count),
+                ask_ct: ask.map_or(0, |l| l.count),
+            }
+        })
+    }
+
+    fn levels(&self, side: Side) -> &BTreeMap<i64, PriceLevel> {
+        if side == Side::Bid {
+            &self.bids
+        } else {
+            &self.asks
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<i64, PriceLevel> {
+        if side == Side::Bid {
+            &mut self.bids
+        } else {
+            &mut self.asks
+        }
+    }
+
+    fn level_mut(&mut self, side: Side, price: Price) -> Result<&mut PriceLevel> {
+        self.levels_mut(side)
+            .get_mut(&price.raw())
+            .ok_or_else(|| Error::Book(format!("Missing {side:?} level at {price}")))
+    }
+
+    fn add(&mut self, order_id: u64, order: Order) -> Result<()> {
+        let levels = self.levels_mut(order.side);
+        let level = levels.entry(order.price.raw()).or_insert(PriceLevel {
+            price: order.price,
+            size: 0,
+            count: 0,
+        });
+        let Some(size) = level.size.checked_add(order.size) else {
+            if level.count == 0 {
+                levels.remove(&order.price.raw());
+            }
+            return Err(level_size_overflow());
+        };
+        level.size = size;
+        level.count += 1;
+        self.orders.insert(order_id, order);
+        Ok(())
+    }
+
+    /// Removes `size` from a resting order, removing the order entirely if no quantity
+    /// remains.
+    fn remove(&mut self, order_id: u64, order: Order, size: u32) -> Result<()> {
+        let level = self.level_mut(order.side, order.price)?;
+        level.size -= size;
+        if size == order.size {
+            level.count -= 1;
+            if level.count == 0 {
+                self.levels_mut(order.side).remove(&order.price.raw());
+            }
+            self.orders.remove(&order_id);
+        } else if let Some(resting) = self.orders.get_mut(&order_id) {
+            resting.size -= size;
+        }
+        Ok(())
+    }
+}
+
+fn order_from(mbo: &MboMsg) -> Result<Order> {
+    match mbo.side()? {
+        side @ (Side::Bid | Side::Ask) => Ok(Order {
+            side,
+            price: mbo.price(),
+            size: mbo.size,
+        }),
+        Side::None => Err(Error::Book(format!(
+            "Received {:?} for order ID {} without a side",
+            mbo.action()?,
+            mbo.order_id
+        ))),
+    }
+}
+
+fn level_size_overflow() -> Error {
+    Error::Book("Total size of price level overflowed".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::raw::c_char;
+
+    use super::*;
+    use crate::test_utils::mbo;
+
+    fn level(price: i64, size: u32, count: u32) -> PriceLevel {
+        PriceLevel {
+            price: Price::from_raw(price),
+            size,
+            count,
+        }
+    }
+
+    fn build(records: &[MboMsg]) -> OrderBook {
+        let mut book = OrderBook::new();
+        for rec in records {
+            book.apply(rec).unwrap();
+        }
+        book
+    }
+
+    #[test]
+    fn test_add() {
+        let book = build(&[
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::Bid, 2, 100, 5),
+            mbo(Action::Add, Side::Bid, 3, 99, 1),
+            mbo(Action::Add, Side::Ask, 4, 101, 7),
+        ]);
+        assert_eq!(
+            book.bbo(),
+            (Some(level(100, 15, 2)), Some(level(101, 7, 1)))
+        );
+        assert_eq!(
+            book.bids().copied().collect::<Vec<_>>(),
+            vec![level(100, 15, 2), level(99, 1, 1)]
+        );
+        assert_eq!(
+            book.order(3),
+            Some(&Order {
+                side: Side::Bid,
+                price: Price::from_raw(99),
+                size: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_modify() {
+        let mut book = build(&[
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::Bid, 2, 100, 5),
+            mbo(Action::Modify, Side::Bid, 1, 100, 3),
+        ]);
+        assert_eq!(book.best_bid(), Some(level(100, 8, 2)));
+        book.apply(&mbo(Action::Modify, Side::Bid, 2, 98, 5))
+            .unwrap();
+        assert_eq!(
+            book.bids().copied().collect::<Vec<_>>(),
+            vec![level(100, 3, 1), level(98, 5, 1)]
+        );
+        // Unknown orders are added
+        book.apply(&mbo(Action::Modify, Side::Ask, 3, 105, 2))
+            .unwrap();
+        assert_eq!(book.best_ask(), Some(level(105, 2, 1)));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut book = build(&[
+            mbo(Action::Add, Side::Ask, 1, 101, 10),
+            mbo(Action::Add, Side::Ask, 2, 102, 5),
+            mbo(Action::Cancel, Side::Ask, 1, 101, 4),
+        ]);
+        assert_eq!(book.best_ask(), Some(level(101, 6, 1)));
+        assert_eq!(book.order(1).unwrap().size, 6);
+        book.apply(&mbo(Action::Cancel, Side::Ask, 1, 101, 6))
+            .unwrap();
+        assert_eq!(book.best_ask(), Some(level(102, 5, 1)));
+        assert!(book.order(1).is_none());
+        assert!(book.level(Side::Ask, Price::from_raw(101)).is_none());
+    }
+
+    #[test]
+    fn test_trade_and_clear() {
+        let mut book = build(&[
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::Ask, 2, 101, 10),
+            mbo(Action::Trade, Side::Ask, 0, 100, 4),
+            mbo(Action::Fill, Side::Bid, 1, 100, 4),
+        ]);
+        assert_eq!(book.best_bid(), Some(level(100, 10, 1)));
+        book.apply(&mbo(Action::Clear, Side::None, 0, 0, 0))
+            .unwrap();
+        assert!(book.is_empty());
+        assert_eq!(book.bbo(), (None, None));
+    }
+
+    #[test]
+    fn test_top_levels() {
+        let book = build(&[
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::Bid, 2, 99, 5),
+            mbo(Action::Add, Side::Ask, 3, 101, 7),
+        ]);
+        let levels = book.top_levels::<3>();
+        assert_eq!(
+            levels[0],
+            BidAskPair {
+                bid_px: 100,
+                ask_px: 101,
+                bid_sz: 10,
+                ask_sz: 7,
+                bid_ct: 1,
+                ask_ct: 1,
+            }
+        );
+        assert_eq!(levels[1].bid_px, 99);
+        assert_eq!(levels[1].ask_px, UNDEF_PRICE);
+        assert_eq!(levels[1].ask_sz, 0);
+        assert_eq!(levels[2].bid_px, UNDEF_PRICE);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut book = build(&[mbo(Action::Add, Side::Bid, 1, 100, 10)]);
+        let before = book.clone();
+        for rec in [
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::None, 2, 100, 10),
+            mbo(Action::Cancel, Side::Bid, 3, 100, 10),
+            mbo(Action::Cancel, Side::Bid, 1, 100, 11),
+            mbo(Action::Add, Side::Bid, 4, 100, u32::MAX),
+        ] {
+            assert!(matches!(book.apply(&rec), Err(Error::Book(_))), "{rec:?}");
+            assert_eq!(book, before);
+        }
+        let mut invalid = mbo(Action::Add, Side::Bid, 5, 100, 1);
+        invalid.action = b'X' as c_char;
+        assert!(matches!(
+            book.apply(&invalid),
+            Err(Error::TypeConversion(_))
+        ));
+        assert_eq!(book, before);
+    }
+
+    #[test]
+    fn test_modify_overflow() {
+        let mut book = build(&[
+            mbo(Action::Add, Side::Bid, 1, 100, 10),
+            mbo(Action::Add, Side::Bid, 2, 98, u32::MAX),
+        ]);
+        let before = book.clone();
+        assert!(matches!(
+            book.apply(&mbo(Action::Modify, Side::Bid, 2, 100, u32::MAX)),
+            Err(Error::Book(_))
+        ));
+        assert_eq!(book, before);
+    }
+}
@@ -0,0 +1,304 @@
+//! A fixed-precision price type for the `i64` price fields of records.
+use std::{fmt, str::FromStr};
+
+use crate::{Error, Result};
+
+/// The number of raw units in a whole unit of price. Every 1 unit of a raw price
+/// corresponds to 1e-9, i.e. 1/1,000,000,000 or 0.000000001.
+pub const FIXED_PRICE_SCALE: i64 = 1_000_000_000;
+/// The raw value of an undefined or missing price.
+pub const UNDEF_PRICE: i64 = i64::MAX;
+
+/// The number of decimal places in a [`Price`].
+const PRICE_DECIMALS: usize = 9;
+/// The string representation of [`Price::UNDEF`].
+const UNDEF_STR: &str = "UNDEF_PRICE";
+
+/// A price expressed as a signed integer where every 1 unit corresponds to 1e-9. A
+/// raw value of [`UNDEF_PRICE`] denotes an undefined price.
+///
+/// Unlike converting to `f64`, formatting with [`Display`](fmt::Display) and parsing
+/// with [`FromStr`] are exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Price(i64);
+
+impl Price {
+    /// The undefined price.
+    pub const UNDEF: Self = Self(UNDEF_PRICE);
+
+    /// Creates a new price from a raw value scaled by [`FIXED_PRICE_SCALE`].
+    pub const fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value scaled by [`FIXED_PRICE_SCALE`].
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// Returns `true` if the price is [`Price::UNDEF`].
+    pub const fn is_undefined(self) -> bool {
+        self.0 == UNDEF_PRICE
+    }
+
+    /// Converts the price to an `f64`, which may lose precision. Returns `NaN` if the
+    /// price is undefined.
+    pub fn to_f64(self) -> f64 {
+        if self.is_undefined() {
+            f64::NAN
+        } else {
+            self.0 as f64 / FIXED_PRICE_SCALE as f64
+        }
+    }
+
+    /// Adds two prices, returning `None` if either is undefined or the result
+    /// overflows.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.checked_op(rhs.defined()?.0, i64::checked_add)
+    }
+
+    /// Subtracts `rhs` from the price, returning `None` if either is undefined or the
+    /// result overflows.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_op(rhs.defined()?.0, i64::checked_sub)
+    }
+
+    /// Multiplies the price by an integer such as a quantity, returning `None` if the
+    /// price is undefined or the result overflows.
+    pub fn checked_mul(self, rhs: i64) -> Option<Self> {
+        self.checked_op(rhs, i64::checked_mul)
+    }
+
+    /// Divides the price by an integer, truncating toward zero. Returns `None` if the
+    /// price is undefined or `rhs` is zero.
+    pub fn checked_div(self, rhs: i64) -> Option<Self> {
+        self.checked_op(rhs, i64::checked_div)
+    }
+
+    /// Rounds the price to the nearest multiple of `tick_size`, rounding halfway cases
+    /// away from zero. Returns `None` if either price is undefined, `tick_size` isn't
+    /// positive, or the result overflows.
+    pub fn round_to_tick(self, tick_size: Self) -> Option<Self> {
+        let tick = tick_size.defined()?.0;
+        if tick <= 0 {
+            return None;
+        }
+        let raw = self.defined()?.0 as i128;
+        let tick = tick as i128;
+        let ticks = if raw >= 0 {
+            (2 * raw + tick) / (2 * tick)
+        } else {
+            (2 * raw - tick) / (2 * tick)
+        };
+        i64::try_from(ticks * tick)
+            .ok()
+            .map(Self)
+            .and_then(Self::defined)
+    }
+
+    fn defined(self) -> Option<Self> {
+        if self.is_undefined() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    fn checked_op(self, rhs: i64, op: fn(i64, i64) -> Option<i64>) -> Option<Self> {
+        op(self.defined()?.0, rhs).map(Self).and_then(Self::defined)
+    }
+}
+
+impl From<i64> for Price {
+    fn from(raw: i64) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Price> for i64 {
+    fn from(price: Price) -> Self {
+        price.0
+    }
+}
+
+/// Formats the price with 9 decimal places, or the number of decimal places given by
+/// the formatter's precision, rounding halfway cases away from zero.
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_undefined() {
+            return f.pad_integral(true, "", UNDEF_STR);
+        }
+        let decimals = f.precision().unwrap_or(PRICE_DECIMALS);
+        let mut abs = self.0.unsigned_abs() as u128;
+        let mut scale = FIXED_PRICE_SCALE as u128;
+        if decimals < PRICE_DECIMALS {
+            let divisor = 10u128.pow((PRICE_DECIMALS - decimals) as u32);
+            abs = (abs + divisor / 2) / divisor;
+            scale /= divisor;
+        }
+        let (int, frac) = (abs / scale, abs % scale);
+        let digits = match decimals {
+            0 => int.to_string(),
+            1..=PRICE_DECIMALS => format!("{int}.{frac:0decimals$}"),
+            _ => format!(
+                "{int}.{frac:0PRICE_DECIMALS$}{:0<pad$}",
+                "",
+                pad = decimals - PRICE_DECIMALS
+            ),
+        };
+        // Precision has already been applied, so only the sign and width are handled here
+        f.pad_integral(self.0 >= 0 || abs == 0, "", &digits)
+    }
+}
+
+/// Parses a decimal string with at most 9 decimal places, such as `"-12.5"`. The
+/// string `"UNDEF_PRICE"` is parsed as [`Price::UNDEF`].
+impl FromStr for Price {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        const INVALID: Error = Error::TypeConversion("Invalid price");
+
+        if s == UNDEF_STR {
+            return Ok(Self::UNDEF);
+        }
+        let (is_negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if (int.is_empty() && frac.is_empty())
+            || frac.len() > PRICE_DECIMALS
+            || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+        {
+            return Err(INVALID);
+        }
+        let mut raw: i128 = 0;
+        for b in int.bytes().chain(frac.bytes()) {
+            raw = raw * 10 + (b - b'0') as i128;
+            if raw > i64::MAX as i128 + 1 {
+                return Err(Error::TypeConversion("Price out of range"));
+            }
+        }
+        raw *= 10i128.pow((PRICE_DECIMALS - frac.len()) as u32);
+        if is_negative {
+            raw = -raw;
+        }
+        match i64::try_from(raw) {
+            Ok(raw) if raw != UNDEF_PRICE => Ok(Self(raw)),
+            _ => Err(Error::TypeConversion("Price out of range")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Price(3722750000000).to_string(), "3722.750000000");
+        assert_eq!(Price(-1).to_string(), "-0.000000001");
+        assert_eq!(Price(0).to_string(), "0.000000000");
+        assert_eq!(Price(i64::MIN).to_string(), "-9223372036.854775808");
+        assert_eq!(Price::UNDEF.to_string(), "UNDEF_PRICE");
+        assert_eq!(format!("{:.2}", Price::UNDEF), "UNDEF_PRICE");
+    }
+
+    #[test]
+    fn test_display_precision() {
+        let price = Price(3722755000000);
+        assert_eq!(format!("{price:.2}"), "3722.76");
+        assert_eq!(format!("{price:.0}"), "3723");
+        assert_eq!(format!("{price:.11}"), "3722.75500000000");
+        assert_eq!(format!("{:.2}", Price(-4_000_000)), "0.00");
+        assert_eq!(format!("{:.2}", Price(-5_000_000)), "-0.01");
+        assert_eq!(format!("{:>10.1}", Price(1_250_000_000)), "       1.3");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("3722.75".parse::<Price>().unwrap(), Price(3722750000000));
+        assert_eq!("-.5".parse::<Price>().unwrap(), Price(-500_000_000));
+        assert_eq!("+7".parse::<Price>().unwrap(), Price(7_000_000_000));
+        assert_eq!("0.000000001".parse::<Price>().unwrap(), Price(1));
+        assert_eq!(
+            "-9223372036.854775808".parse::<Price>().unwrap(),
+            Price(i64::MIN)
+        );
+        assert_eq!("UNDEF_PRICE".parse::<Price>().unwrap(), Price::UNDEF);
+        for invalid in [
+            "",
+            "-",
+            ".",
+            "1.0000000001",
+            "1e9",
+            "1,000",
+            " 1",
+            "9223372036.854775807",
+            "99999999999",
+        ] {
+            assert!(invalid.parse::<Price>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_display_from_str_round_trip() {
+        for raw in [0, 1, -1, 123_456_789_012, i64::MIN, i64::MAX - 1] {
+            let price = Price(raw);
+            assert_eq!(price.to_string().parse::<Price>().unwrap(), price);
+        }
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(Price(3722750000000).to_f64(), 3722.75);
+        assert!(Price::UNDEF.to_f64().is_nan());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let price = Price(1_500_000_000);
+        assert_eq!(price.checked_add(Price(1)), Some(Price(1_500_000_001)));
+        assert_eq!(price.checked_sub(Price(1)), Some(Price(1_499_999_999)));
+        assert_eq!(price.checked_mul(3), Some(Price(4_500_000_000)));
+        assert_eq!(price.checked_div(2), Some(Price(750_000_000)));
+        assert_eq!(price.checked_div(0), None);
+        assert_eq!(price.checked_add(Price::UNDEF), None);
+        assert_eq!(Price::UNDEF.checked_sub(price), None);
+        assert_eq!(Price(i64::MAX - 1).checked_add(Price(1)), None);
+        assert_eq!(Price(i64::MIN).checked_sub(Price(1)), None);
+    }
+
+    #[test]
+    fn test_round_to_tick() {
+        let tick = Price(250_000_000);
+        assert_eq!(
+            Price(3_722_120_000_000).round_to_tick(tick),
+            Some(Price(3_722_000_000_000))
+        );
+        assert_eq!(
+            Price(3_722_125_000_000).round_to_tick(tick),
+            Some(Price(3_722_250_000_000))
+        );
+        assert_eq!(
+            Price(-3_722_125_000_000).round_to_tick(tick),
+            Some(Price(-3_722_250_000_000))
+        );
+        assert_eq!(Price(3).round_to_tick(Price(3)), Some(Price(3)));
+        assert_eq!(Price(4).round_to_tick(Price(3)), Some(Price(3)));
+        assert_eq!(Price(5).round_to_tick(Price(3)), Some(Price(6)));
+        assert_eq!(Price(1).round_to_tick(Price(0)), None);
+        assert_eq!(Price::UNDEF.round_to_tick(tick), None);
+        assert_eq!(
+            Price(i64::MAX - 1).round_to_tick(Price(FIXED_PRICE_SCALE)),
+            None
+        );
+    }
+}
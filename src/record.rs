@@ -1,13 +1,22 @@
-//! Market data types for encoding different Databento [`Schema`](crate::enums::Schema)s and conversion functions.
+//! Market data types for encoding different Databento [`Schema`]s and conversion functions.
 use std::{mem, ops::RangeInclusive, os::raw::c_char, ptr::NonNull};
 
-use crate::enums::SecurityUpdateAction;
+use crate::{
+    enums::{
+        Action, AuctionStatus, AuctionType, FreezeStatus, HaltReason, MatchAlgorithm, Schema,
+        SecurityTradingStatus, SecurityUpdateAction, SettlPriceType, Side, StatType,
+        StatUpdateAction, TradingEvent, UnderlyingProduct, UserDefinedInstrument,
+    },
+    price::UNDEF_PRICE,
+    timestamp::UNDEF_TIMESTAMP,
+    Duration, FixedStr, Flags, Price, Timestamp,
+};
 
 /// Common data for all Databento records.
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecordHeader {
     /// The length of the message in 32-bit words.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -21,7 +30,13 @@ pub struct RecordHeader {
     /// The product ID assigned by the venue.
     pub product_id: u32,
     /// The matching engine received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_event: u64,
 }
 
@@ -31,9 +46,13 @@ pub const TICK_MSG_TYPE_ID: u8 = 0xA0;
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MboMsg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<MboMsg, _>")
+    )]
     pub hd: RecordHeader,
     /// The order ID assigned at the venue.
     pub order_id: u64,
@@ -42,7 +61,7 @@ pub struct MboMsg {
     pub price: i64,
     /// The order quantity.
     pub size: u32,
-    /// A combination of packet end with matching engine status.
+    /// A combination of packet end with matching engine status. See [`Flags`].
     pub flags: u8,
     /// A channel ID within the venue.
     pub channel_id: u8,
@@ -52,7 +71,13 @@ pub struct MboMsg {
     /// The order side. Can be A\[sk\], B\[id\] or N\[one\].
     pub side: c_char,
     /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
     /// The delta of `ts_recv - ts_exchange_send`, max 2 seconds.
     pub ts_in_delta: i32,
@@ -65,7 +90,7 @@ pub struct MboMsg {
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BidAskPair {
     /// The bid price.
     pub bid_px: i64,
@@ -89,9 +114,13 @@ pub const MBP_MSG_TYPE_ID_RANGE: RangeInclusive<u8> = 0x00..=(MAX_UA_BOOK_LEVEL
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TradeMsg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<TradeMsg, _>")
+    )]
     pub hd: RecordHeader,
     /// The order price expressed as a signed integer where every 1 unit
     /// corresponds to 1e-9, i.e. 1/1,000,000,000 or 0.000000001.
@@ -103,12 +132,18 @@ pub struct TradeMsg {
     pub action: c_char,
     /// The order side. Can be A\[sk\], B\[id\] or N\[one\].
     pub side: c_char,
-    /// A combination of packet end with matching engine status.
+    /// A combination of packet end with matching engine status. See [`Flags`].
     pub flags: u8,
     /// The depth of actual book change.
     pub depth: u8,
     /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
     /// The delta of `ts_recv - ts_exchange_send`, max 2 seconds.
     pub ts_in_delta: i32,
@@ -122,9 +157,13 @@ pub struct TradeMsg {
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbp1Msg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<Mbp1Msg, _>")
+    )]
     pub hd: RecordHeader,
     /// The order price expressed as a signed integer where every 1 unit
     /// corresponds to 1e-9, i.e. 1/1,000,000,000 or 0.000000001.
@@ -136,12 +175,18 @@ pub struct Mbp1Msg {
     pub action: c_char,
     /// The order side. Can be A\[sk\], B\[id\] or N\[one\].
     pub side: c_char,
-    /// A combination of packet end with matching engine status.
+    /// A combination of packet end with matching engine status. See [`Flags`].
     pub flags: u8,
     /// The depth of actual book change.
     pub depth: u8,
     /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
     /// The delta of `ts_recv - ts_exchange_send`, max 2 seconds.
     pub ts_in_delta: i32,
@@ -154,9 +199,13 @@ pub struct Mbp1Msg {
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbp10Msg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<Mbp10Msg, _>")
+    )]
     pub hd: RecordHeader,
     /// The order price expressed as a signed integer where every 1 unit
     /// corresponds to 1e-9, i.e. 1/1,000,000,000 or 0.000000001.
@@ -168,12 +217,18 @@ pub struct Mbp10Msg {
     pub action: c_char,
     /// The order side. Can be A\[sk\], B\[id\] or N\[one\].
     pub side: c_char,
-    /// A combination of packet end with matching engine status.
+    /// A combination of packet end with matching engine status. See [`Flags`].
     pub flags: u8,
     /// The depth of actual book change.
     pub depth: u8,
     /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
     /// The delta of `ts_recv - ts_exchange_send`, max 2 seconds.
     pub ts_in_delta: i32,
@@ -189,9 +244,13 @@ pub const OHLCV_TYPE_ID: u8 = 0x11;
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OhlcvMsg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<OhlcvMsg, _>")
+    )]
     pub hd: RecordHeader,
     /// The open price for the bar.
     pub open: i64,
@@ -211,17 +270,34 @@ pub const STATUS_MSG_TYPE_ID: u8 = 0x12;
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatusMsg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<StatusMsg, _>")
+    )]
     pub hd: RecordHeader,
     /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub group: [c_char; 21],
+    /// The security group code the status applies to. Use [`StatusMsg::group()`] to
+    /// convert it to a `&str`.
+    pub group: FixedStr<21>,
+    /// The trading status of the group. Use [`StatusMsg::trading_status()`] to parse
+    /// it.
     pub trading_status: u8,
+    /// The reason for the change in trading status. Use [`StatusMsg::halt_reason()`]
+    /// to parse it.
     pub halt_reason: u8,
+    /// An additional event accompanying the change in trading status. Use
+    /// [`StatusMsg::trading_event()`] to parse it.
     pub trading_event: u8,
 }
 
@@ -231,90 +307,192 @@ pub const INSTRUMENT_DEF_MSG_TYPE_ID: u8 = 0x13;
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
-#[doc(hidden)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InstrumentDefMsg {
     /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<InstrumentDefMsg, _>")
+    )]
     pub hd: RecordHeader,
     /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
+    /// The minimum constant tick for the instrument in units of 1e-9, i.e.
+    /// 1/1,000,000,000 or 0.000000001.
     pub min_price_increment: i64,
+    /// The multiplier to convert the venue's display price to the conventional price.
     pub display_factor: i64,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    /// The last eligible trade time expressed as a number of nanoseconds since the
+    /// UNIX epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub expiration: u64,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    /// The time of instrument activation expressed as a number of nanoseconds since the
+    /// UNIX epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub activation: u64,
+    /// The allowable high limit price for the trading day in units of 1e-9, i.e.
+    /// 1/1,000,000,000 or 0.000000001.
     pub high_limit_price: i64,
+    /// The allowable low limit price for the trading day in units of 1e-9, i.e.
+    /// 1/1,000,000,000 or 0.000000001.
     pub low_limit_price: i64,
+    /// The differential value for price banding in units of 1e-9, i.e.
+    /// 1/1,000,000,000 or 0.000000001.
     pub max_price_variation: i64,
+    /// The trading session settlement price on `trading_reference_date`.
     pub trading_reference_price: i64,
+    /// The contract size for each instrument, in combination with `unit_of_measure`.
     pub unit_of_measure_qty: i64,
+    /// The value of one tick, i.e. `min_price_increment` multiplied by the contract
+    /// size, in units of 1e-9, i.e. 1/1,000,000,000 or 0.000000001.
     pub min_price_increment_amount: i64,
+    /// The value used for price calculation in spread and leg pricing in units of 1e-9,
+    /// i.e. 1/1,000,000,000 or 0.000000001.
     pub price_ratio: i64,
+    /// A bitmap of instrument eligibility attributes.
     pub inst_attrib_value: i32,
+    /// The `product_id` of the first underlying instrument.
     pub underlying_id: u32,
+    /// The total cleared volume of the instrument traded during the prior trading
+    /// session.
     pub cleared_volume: i32,
+    /// The implied book depth on the price level data feed.
     pub market_depth_implied: i32,
+    /// The (outright) book depth on the price level data feed.
     pub market_depth: i32,
+    /// The market segment of the instrument.
     pub market_segment_id: u32,
+    /// The maximum trading volume for the instrument.
     pub max_trade_vol: u32,
+    /// The minimum order entry quantity for the instrument.
     pub min_lot_size: i32,
+    /// The minimum quantity required for a block trade of the instrument.
     pub min_lot_size_block: i32,
+    /// The minimum quantity required for a round lot of the instrument. Multiples of
+    /// this quantity are also round lots.
     pub min_lot_size_round_lot: i32,
+    /// The minimum trading volume for the instrument.
     pub min_trade_vol: u32,
+    /// The total open interest for the market at the close of the prior trading session.
     pub open_interest_qty: i32,
+    /// The number of deliverables per instrument, i.e. peak days.
     pub contract_multiplier: i32,
+    /// The quantity that a contract will decay daily, after `decay_start_date` has
+    /// been reached.
     pub decay_quantity: i32,
+    /// The fixed contract value assigned to each instrument.
     pub original_contract_size: i32,
+    /// The `product_id` of a related instrument.
     pub related_security_id: u32,
+    /// The trading session date corresponding to the settlement price in
+    /// `trading_reference_price`, in number of days since the UNIX epoch.
     pub trading_reference_date: u16,
+    /// The channel ID assigned at the venue.
     pub appl_id: i16,
+    /// The calendar year reflected in the instrument symbol.
     pub maturity_year: u16,
+    /// The date at which a contract will begin to decay.
     pub decay_start_date: u16,
+    /// The channel ID assigned by Databento as an incrementing integer starting at zero.
     pub channel_id: u16,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub currency: [c_char; 4],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub settl_currency: [c_char; 4],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub secsubtype: [c_char; 6],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub symbol: [c_char; 22],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub group: [c_char; 21],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub exchange: [c_char; 5],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub asset: [c_char; 7],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub cfi: [c_char; 7],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub security_type: [c_char; 7],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub unit_of_measure: [c_char; 31],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub underlying: [c_char; 21],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub related: [c_char; 21],
+    /// The currency used for price fields. Use [`InstrumentDefMsg::currency()`] to
+    /// convert it to a `&str`.
+    pub currency: FixedStr<4>,
+    /// The currency used for settlement, if different from `currency`. Use
+    /// [`InstrumentDefMsg::settl_currency()`] to convert it to a `&str`.
+    pub settl_currency: FixedStr<4>,
+    /// The strategy type of the spread. Use [`InstrumentDefMsg::secsubtype()`] to
+    /// convert it to a `&str`.
+    pub secsubtype: FixedStr<6>,
+    /// The instrument raw symbol assigned by the publisher. Use
+    /// [`InstrumentDefMsg::symbol()`] to convert it to a `&str`.
+    pub symbol: FixedStr<22>,
+    /// The security group code of the instrument. Use [`InstrumentDefMsg::group()`] to
+    /// convert it to a `&str`.
+    pub group: FixedStr<21>,
+    /// The exchange used to identify the instrument. Use
+    /// [`InstrumentDefMsg::exchange()`] to convert it to a `&str`.
+    pub exchange: FixedStr<5>,
+    /// The underlying asset code (product code) of the instrument. Use
+    /// [`InstrumentDefMsg::asset()`] to convert it to a `&str`.
+    pub asset: FixedStr<7>,
+    /// The ISO standard instrument categorization code. Use [`InstrumentDefMsg::cfi()`]
+    /// to convert it to a `&str`.
+    pub cfi: FixedStr<7>,
+    /// The type of the instrument, e.g. FUT for future or future spread. Use
+    /// [`InstrumentDefMsg::security_type()`] to convert it to a `&str`.
+    pub security_type: FixedStr<7>,
+    /// The unit of measure for the instrument's original contract size, e.g. USD or
+    /// LBS. Use [`InstrumentDefMsg::unit_of_measure()`] to convert it to a `&str`.
+    pub unit_of_measure: FixedStr<31>,
+    /// The symbol of the first underlying instrument. Use
+    /// [`InstrumentDefMsg::underlying()`] to convert it to a `&str`.
+    pub underlying: FixedStr<21>,
+    /// The symbol of a related instrument. Use [`InstrumentDefMsg::related()`] to
+    /// convert it to a `&str`.
+    pub related: FixedStr<21>,
+    /// The matching algorithm used for the instrument, typically **F**IFO. Use
+    /// [`InstrumentDefMsg::match_algorithm()`] to parse it.
     pub match_algorithm: c_char,
+    /// The current trading state of the instrument. Use
+    /// [`InstrumentDefMsg::md_security_trading_status()`] to parse it.
     pub md_security_trading_status: u8,
+    /// The price denominator of the main fraction.
     pub main_fraction: u8,
+    /// The number of digits to the right of the tick mark, to display fractional
+    /// prices.
     pub price_display_format: u8,
+    /// The type indicators for the settlement price, as a bitmap. Use
+    /// [`InstrumentDefMsg::settl_price_type()`] to parse it.
     pub settl_price_type: u8,
+    /// The price denominator of the sub fraction.
     pub sub_fraction: u8,
+    /// The product complex of the instrument. Use
+    /// [`InstrumentDefMsg::underlying_product()`] to parse it.
     pub underlying_product: u8,
+    /// The type of definition update. Can be A\[dd\], M\[odify\], or D\[elete\].
+    /// Use [`InstrumentDefMsg::security_update_action()`] to parse it.
     #[cfg_attr(
         feature = "serde",
-        serde(serialize_with = "serialize_enum_as_char_repr")
+        serde(
+            serialize_with = "serialize_c_char",
+            deserialize_with = "deserialize_c_char"
+        )
     )]
-    pub security_update_action: SecurityUpdateAction,
+    pub security_update_action: c_char,
+    /// The calendar month reflected in the instrument symbol.
     pub maturity_month: u8,
+    /// The calendar day reflected in the instrument symbol, or 0.
     pub maturity_day: u8,
+    /// The calendar week reflected in the instrument symbol, or 0.
     pub maturity_week: u8,
+    /// Indicates if the instrument is user defined: **Y**es or **N**o. Use
+    /// [`InstrumentDefMsg::user_defined_instrument()`] to parse it.
     pub user_defined_instrument: c_char,
+    /// The type of `contract_multiplier`. Either `1` for hours, or `2` for days.
     pub contract_multiplier_unit: i8,
+    /// The schedule for delivering electricity.
     pub flow_schedule_type: i8,
+    /// The tick rule of the spread.
     pub tick_rule: u8,
     /// Adjust filler for alignment.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -322,40 +500,78 @@ pub struct InstrumentDefMsg {
 }
 
 pub const IMBALANCE_TYPE_ID: u8 = 0x14;
-/// Order imbalance message.
+/// Order imbalance message for an auction.
+/// `hd.rtype = 0x14`
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
-#[doc(hidden)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Imbalance {
+    /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<Imbalance, _>")
+    )]
     pub hd: RecordHeader,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    /// The capture server received timestamp expressed as number of nanoseconds since
+    /// UNIX epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub ts_recv: u64,
+    /// The price at which the imbalance shares are calculated, where every 1 unit
+    /// corresponds to 1e-9, i.e. 1/1,000,000,000 or 0.000000001.
     pub ref_price: i64,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_large_u64"))]
+    /// The time of the auction expressed as a number of nanoseconds since the UNIX
+    /// epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
     pub auction_time: u64,
     /// Continuous book clearing price.
     pub cont_book_clr_price: i64,
     /// Auction interest clearing price.
     pub auct_interest_clr_price: i64,
-    // Short-selling restriction filling price.
+    /// Short-selling restriction filling price.
     pub ssr_filling_price: i64,
     /// Indicative match price.
     pub ind_match_price: i64,
+    /// The upper limit of the auction price collar.
     pub upper_collar: i64,
+    /// The lower limit of the auction price collar.
     pub lower_collar: i64,
+    /// The quantity of shares that are eligible to be matched at `ref_price`.
     pub paired_qty: u32,
+    /// The quantity of shares that are not paired at `ref_price`.
     pub total_imbalance_qty: u32,
+    /// The quantity of market orders that are not paired.
     pub market_imbalance_qty: u32,
+    /// The type of auction. Use [`Imbalance::auction_type()`] to parse it.
     pub auction_type: c_char,
+    /// The side of `total_imbalance_qty`. Use [`Imbalance::side()`] to parse it.
     pub side: c_char,
+    /// Whether the auction will run. Use [`Imbalance::auction_status()`] to parse it.
     pub auction_status: u8,
+    /// Whether the book is frozen. Use [`Imbalance::freeze_status()`] to parse it.
     pub freeze_status: u8,
+    /// The number of times the halt period has been extended.
     pub num_extensions: u8,
+    /// The quantity of shares that are not paired at `ref_price`, in round lots.
     pub unpaired_qty: u8,
+    /// The side of `unpaired_qty`. Use [`Imbalance::unpaired_side()`] to parse it.
     pub unpaired_side: c_char,
+    /// A venue-specific character code. For Nasdaq, contains the raw price variation
+    /// indicator.
     pub significant_imbalance: c_char,
+    /// Adjust filler for alignment.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub _dummy: [c_char; 4],
 }
@@ -366,11 +582,14 @@ pub const GATEWAY_ERROR_MSG_TYPE_ID: u8 = 0x15;
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GatewayErrorMsg {
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<GatewayErrorMsg, _>")
+    )]
     pub hd: RecordHeader,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub err: [c_char; 64],
+    pub err: FixedStr<64>,
 }
 
 pub const SYMBOL_MAPPING_MSG_TYPE_ID: u8 = 0x16;
@@ -379,46 +598,151 @@ pub const SYMBOL_MAPPING_MSG_TYPE_ID: u8 = 0x16;
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trivial_copy", derive(Copy))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SymbolMappingMsg {
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<SymbolMappingMsg, _>")
+    )]
     pub hd: RecordHeader,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub stype_in_symbol: [c_char; 22],
-    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_c_char_arr"))]
-    pub stype_out_symbol: [c_char; 22],
+    pub stype_in_symbol: FixedStr<22>,
+    pub stype_out_symbol: FixedStr<22>,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub _dummy: [c_char; 4],
     pub start_ts: u64,
     pub end_ts: u64,
 }
 
+pub const STAT_MSG_TYPE_ID: u8 = 0x18;
+/// A statistic published by a venue, such as a settlement price or the open interest.
+/// `hd.rtype = 0x18`
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "trivial_copy", derive(Copy))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatMsg {
+    /// The common header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_header::<StatMsg, _>")
+    )]
+    pub hd: RecordHeader,
+    /// The capture server received timestamp expressed as number of nanoseconds since UNIX epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
+    pub ts_recv: u64,
+    /// The reference timestamp of the statistic, such as the trading date of a
+    /// settlement price, expressed as number of nanoseconds since UNIX epoch. Will be
+    /// [`UNDEF_TIMESTAMP`] when unused.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_large_u64",
+            deserialize_with = "deserialize_large_u64"
+        )
+    )]
+    pub ts_ref: u64,
+    /// The value of price statistics where every 1 unit corresponds to 1e-9, i.e.
+    /// 1/1,000,000,000 or 0.000000001. Will be
+    /// [`UNDEF_PRICE`] for statistics without a price.
+    pub price: i64,
+    /// The value of quantity statistics, such as the open interest or cleared volume.
+    /// Will be [`i32::MAX`] for statistics without a quantity.
+    pub quantity: i32,
+    /// The message sequence number assigned at the venue.
+    pub sequence: u32,
+    /// The delta of `ts_recv - ts_exchange_send`, max 2 seconds.
+    pub ts_in_delta: i32,
+    /// The type of statistic. See [`StatType`].
+    pub stat_type: u16,
+    /// The channel ID assigned by Databento as an incrementing integer starting at zero.
+    pub channel_id: u16,
+    /// Whether the statistic is new or deletes a previous value. See
+    /// [`StatUpdateAction`].
+    pub update_action: u8,
+    /// Additional flags associated with certain stat types.
+    pub stat_flags: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _dummy: [c_char; 6],
+}
+
+/// Serialize as a string to avoid any loss of precision with JSON serializers and parsers.
 #[cfg(feature = "serde")]
-fn serialize_c_char_arr<S: serde::Serializer, const N: usize>(
-    arr: &[c_char; N],
+pub(crate) fn serialize_large_u64<S: serde::Serializer>(
+    num: &u64,
     serializer: S,
 ) -> Result<S::Ok, S::Error> {
-    let cstr = unsafe { std::ffi::CStr::from_ptr(&arr[0]) };
-    let str = cstr.to_str().unwrap_or("<invalid UTF-8>");
-    serializer.serialize_str(str)
+    serializer.serialize_str(&num.to_string())
 }
 
-/// Serialize as a string to avoid any loss of precision with JSON serializers and parsers.
+/// Deserialize a `u64` from either a string or an integer.
 #[cfg(feature = "serde")]
-fn serialize_large_u64<S: serde::Serializer>(num: &u64, serializer: S) -> Result<S::Ok, S::Error> {
-    serializer.serialize_str(&num.to_string())
+pub(crate) fn deserialize_large_u64<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    struct LargeU64Visitor;
+
+    impl<'de> serde::de::Visitor<'de> for LargeU64Visitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("an unsigned 64-bit integer or a string containing one")
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<u64, E> {
+            Ok(v)
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<u64, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(LargeU64Visitor)
 }
 
-/// Serialize an enum as its char representation.
+/// Deserialize a [`RecordHeader`], filling in the `length` of record type `T`, which
+/// isn't serialized.
 #[cfg(feature = "serde")]
-fn serialize_enum_as_char_repr<S: serde::Serializer, T: Copy + Into<u8>>(
-    val: &T,
-    serializer: S,
-) -> Result<S::Ok, S::Error> {
-    serializer.serialize_char(Into::<u8>::into(*val) as char)
+fn deserialize_header<'de, T: ConstTypeId, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<RecordHeader, D::Error> {
+    let mut header = <RecordHeader as serde::Deserialize>::deserialize(deserializer)?;
+    header.length = (mem::size_of::<T>() / 4) as u8;
+    Ok(header)
+}
+
+/// Serialize a `c_char` as a `char`.
+#[cfg(feature = "serde")]
+fn serialize_c_char<S: serde::Serializer>(c: &c_char, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_char(*c as u8 as char)
+}
+
+/// Deserialize a `c_char` from a `char`.
+#[cfg(feature = "serde")]
+fn deserialize_c_char<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<c_char, D::Error> {
+    let c = <char as serde::Deserialize>::deserialize(deserializer)?;
+    u8::try_from(c).map(|b| b as c_char).map_err(|_| {
+        serde::de::Error::invalid_value(serde::de::Unexpected::Char(c), &"an ASCII character")
+    })
 }
 
 /// A trait for objects with polymorphism based around [`RecordHeader::rtype`].
-pub trait ConstTypeId {
+///
+/// # Safety
+/// Implementing types must be `#[repr(C)]`, begin with a [`RecordHeader`], have an
+/// alignment no greater than that of [`RecordHeader`], contain no padding bytes, and
+/// be valid for any bit pattern.
+/// [`RecordRef`](crate::RecordRef) relies on this to provide safe views of raw
+/// record bytes.
+pub unsafe trait ConstTypeId {
     /// The value of [`RecordHeader::rtype`] for the implementing type.
     const TYPE_ID: u8;
 }
@@ -446,6 +770,10 @@ pub unsafe fn transmute_into_header<T: ConstTypeId>(record: &T) -> &RecordHeader
 /// # Safety
 /// `raw` must contain at least `std::mem::size_of::<T>()` bytes and a valid
 /// [`RecordHeader`] instance.
+#[deprecated(
+    since = "0.5.0",
+    note = "Use `RecordRef::try_from_bytes` and `RecordRef::get` instead"
+)]
 pub unsafe fn transmute_record_bytes<T: ConstTypeId>(bytes: &[u8]) -> Option<&T> {
     assert!(
         bytes.len() >= mem::size_of::<T>(),
@@ -471,6 +799,7 @@ pub unsafe fn transmute_record_bytes<T: ConstTypeId>(bytes: &[u8]) -> Option<&T>
 /// # Safety
 /// `bytes` must contain a complete record (not only the header). This is so that
 /// the header can be subsequently passed to transmute_record
+#[deprecated(since = "0.5.0", note = "Use `RecordRef::try_from_bytes` instead")]
 pub unsafe fn transmute_header_bytes(bytes: &[u8]) -> Option<&RecordHeader> {
     assert!(
         bytes.len() >= mem::size_of::<RecordHeader>(),
@@ -496,6 +825,7 @@ pub unsafe fn transmute_header_bytes(bytes: &[u8]) -> Option<&RecordHeader> {
 /// # Safety
 /// Although this function accepts a reference to a [`RecordHeader`], it's assumed this is
 /// part of a larger `T` struct.
+#[deprecated(since = "0.5.0", note = "Use `RecordRef::get` instead")]
 pub unsafe fn transmute_record<T: ConstTypeId>(header: &RecordHeader) -> Option<&T> {
     if header.rtype == T::TYPE_ID {
         // Safety: because it comes from a reference, `header` must not be null. It's ok to cast to `mut`
@@ -514,6 +844,7 @@ pub unsafe fn transmute_record<T: ConstTypeId>(header: &RecordHeader) -> Option<
 /// # Safety
 /// Although this function accepts a reference to a [`RecordHeader`], it's assumed this is
 /// part of a larger `T` struct.
+#[deprecated(since = "0.5.0", note = "Use `RecordRef::get` instead")]
 pub unsafe fn transmute_record_mut<T: ConstTypeId>(header: &mut RecordHeader) -> Option<&mut T> {
     if header.rtype == T::TYPE_ID {
         // Safety: because it comes from a reference, `header` must not be null. It's ok to cast to `mut`
@@ -525,70 +856,988 @@ pub unsafe fn transmute_record_mut<T: ConstTypeId>(header: &mut RecordHeader) ->
     }
 }
 
-impl ConstTypeId for MboMsg {
+unsafe impl ConstTypeId for MboMsg {
     const TYPE_ID: u8 = TICK_MSG_TYPE_ID;
 }
 
 /// [TradeMsg]'s type ID is the size of its `booklevel` array (0) and is
 /// equivalent to MBP-0.
-impl ConstTypeId for TradeMsg {
+unsafe impl ConstTypeId for TradeMsg {
     const TYPE_ID: u8 = 0;
 }
 
 /// [Mbp1Msg]'s type ID is the size of its `booklevel` array.
-impl ConstTypeId for Mbp1Msg {
+unsafe impl ConstTypeId for Mbp1Msg {
     const TYPE_ID: u8 = 1;
 }
 
 /// [Mbp10Msg]'s type ID is the size of its `booklevel` array.
-impl ConstTypeId for Mbp10Msg {
+unsafe impl ConstTypeId for Mbp10Msg {
     const TYPE_ID: u8 = 10;
 }
 
-impl ConstTypeId for OhlcvMsg {
+unsafe impl ConstTypeId for OhlcvMsg {
     const TYPE_ID: u8 = OHLCV_TYPE_ID;
 }
 
-impl ConstTypeId for StatusMsg {
+unsafe impl ConstTypeId for StatusMsg {
     const TYPE_ID: u8 = STATUS_MSG_TYPE_ID;
 }
 
-impl ConstTypeId for InstrumentDefMsg {
+unsafe impl ConstTypeId for InstrumentDefMsg {
     const TYPE_ID: u8 = INSTRUMENT_DEF_MSG_TYPE_ID;
 }
 
-impl ConstTypeId for Imbalance {
+unsafe impl ConstTypeId for Imbalance {
     const TYPE_ID: u8 = IMBALANCE_TYPE_ID;
 }
 
-impl ConstTypeId for GatewayErrorMsg {
+unsafe impl ConstTypeId for GatewayErrorMsg {
     const TYPE_ID: u8 = GATEWAY_ERROR_MSG_TYPE_ID;
 }
 
-impl ConstTypeId for SymbolMappingMsg {
+unsafe impl ConstTypeId for SymbolMappingMsg {
     const TYPE_ID: u8 = SYMBOL_MAPPING_MSG_TYPE_ID;
 }
 
+unsafe impl ConstTypeId for StatMsg {
+    const TYPE_ID: u8 = STAT_MSG_TYPE_ID;
+}
+
+/// A trait for record types that make up one or more [`Schema`]s.
+pub trait HasSchema: ConstTypeId {
+    /// The schemas whose records are of the implementing type.
+    const SCHEMAS: &'static [Schema];
+}
+
+impl HasSchema for MboMsg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Mbo];
+}
+
+impl HasSchema for TradeMsg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Trades];
+}
+
+impl HasSchema for Mbp1Msg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Mbp1, Schema::Tbbo];
+}
+
+impl HasSchema for Mbp10Msg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Mbp10];
+}
+
+impl HasSchema for OhlcvMsg {
+    const SCHEMAS: &'static [Schema] = &[
+        Schema::Ohlcv1S,
+        Schema::Ohlcv1M,
+        Schema::Ohlcv1H,
+        Schema::Ohlcv1D,
+    ];
+}
+
+impl HasSchema for StatusMsg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Status];
+}
+
+impl HasSchema for InstrumentDefMsg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Definition];
+}
+
+impl HasSchema for StatMsg {
+    const SCHEMAS: &'static [Schema] = &[Schema::Statistics];
+}
+
+impl RecordHeader {
+    /// Creates a header for a record of type `T`, deriving `length` from the size of
+    /// `T` and `rtype` from [`ConstTypeId::TYPE_ID`].
+    pub const fn new<T: ConstTypeId>(publisher_id: u16, product_id: u32, ts_event: u64) -> Self {
+        Self {
+            length: (mem::size_of::<T>() / 4) as u8,
+            rtype: T::TYPE_ID,
+            publisher_id,
+            product_id,
+            ts_event,
+        }
+    }
+
+    /// Returns the matching engine received timestamp as a [`Timestamp`].
+    pub fn ts_event(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_event)
+    }
+}
+
+impl MboMsg {
+    /// Returns the order price as a [`Price`].
+    pub fn price(&self) -> Price {
+        Price::from_raw(self.price)
+    }
+
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the delta of `ts_recv - ts_exchange_send` as a [`Duration`].
+    pub fn ts_in_delta(&self) -> Duration {
+        Duration::from_nanos(self.ts_in_delta as i64)
+    }
+
+    /// Returns the flags of the record.
+    pub fn flags(&self) -> Flags {
+        Flags::from_raw(self.flags)
+    }
+
+    /// Parses the raw `action` into an [`Action`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown action.
+    pub fn action(&self) -> crate::Result<Action> {
+        Action::try_from(self.action)
+    }
+
+    /// Parses the raw `side` into a [`Side`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown side.
+    pub fn side(&self) -> crate::Result<Side> {
+        Side::try_from(self.side)
+    }
+}
+
+impl TradeMsg {
+    /// Returns the order price as a [`Price`].
+    pub fn price(&self) -> Price {
+        Price::from_raw(self.price)
+    }
+
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the delta of `ts_recv - ts_exchange_send` as a [`Duration`].
+    pub fn ts_in_delta(&self) -> Duration {
+        Duration::from_nanos(self.ts_in_delta as i64)
+    }
+
+    /// Returns the flags of the record.
+    pub fn flags(&self) -> Flags {
+        Flags::from_raw(self.flags)
+    }
+
+    /// Parses the raw `action` into an [`Action`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown action.
+    pub fn action(&self) -> crate::Result<Action> {
+        Action::try_from(self.action)
+    }
+
+    /// Parses the raw `side` into a [`Side`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown side.
+    pub fn side(&self) -> crate::Result<Side> {
+        Side::try_from(self.side)
+    }
+}
+
+impl Mbp1Msg {
+    /// Returns the order price as a [`Price`].
+    pub fn price(&self) -> Price {
+        Price::from_raw(self.price)
+    }
+
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the delta of `ts_recv - ts_exchange_send` as a [`Duration`].
+    pub fn ts_in_delta(&self) -> Duration {
+        Duration::from_nanos(self.ts_in_delta as i64)
+    }
+
+    /// Returns the flags of the record.
+    pub fn flags(&self) -> Flags {
+        Flags::from_raw(self.flags)
+    }
+
+    /// Parses the raw `action` into an [`Action`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown action.
+    pub fn action(&self) -> crate::Result<Action> {
+        Action::try_from(self.action)
+    }
+
+    /// Parses the raw `side` into a [`Side`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown side.
+    pub fn side(&self) -> crate::Result<Side> {
+        Side::try_from(self.side)
+    }
+}
+
+impl Mbp10Msg {
+    /// Returns the order price as a [`Price`].
+    pub fn price(&self) -> Price {
+        Price::from_raw(self.price)
+    }
+
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the delta of `ts_recv - ts_exchange_send` as a [`Duration`].
+    pub fn ts_in_delta(&self) -> Duration {
+        Duration::from_nanos(self.ts_in_delta as i64)
+    }
+
+    /// Returns the flags of the record.
+    pub fn flags(&self) -> Flags {
+        Flags::from_raw(self.flags)
+    }
+
+    /// Parses the raw `action` into an [`Action`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown action.
+    pub fn action(&self) -> crate::Result<Action> {
+        Action::try_from(self.action)
+    }
+
+    /// Parses the raw `side` into a [`Side`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown side.
+    pub fn side(&self) -> crate::Result<Side> {
+        Side::try_from(self.side)
+    }
+}
+
+impl BidAskPair {
+    /// Returns the bid price as a [`Price`].
+    pub fn bid_px(&self) -> Price {
+        Price::from_raw(self.bid_px)
+    }
+
+    /// Returns the ask price as a [`Price`].
+    pub fn ask_px(&self) -> Price {
+        Price::from_raw(self.ask_px)
+    }
+}
+
+impl OhlcvMsg {
+    /// Returns the open price for the bar as a [`Price`].
+    pub fn open(&self) -> Price {
+        Price::from_raw(self.open)
+    }
+
+    /// Returns the high price for the bar as a [`Price`].
+    pub fn high(&self) -> Price {
+        Price::from_raw(self.high)
+    }
+
+    /// Returns the low price for the bar as a [`Price`].
+    pub fn low(&self) -> Price {
+        Price::from_raw(self.low)
+    }
+
+    /// Returns the close price for the bar as a [`Price`].
+    pub fn close(&self) -> Price {
+        Price::from_raw(self.close)
+    }
+}
+
+impl InstrumentDefMsg {
+    /// Returns the minimum price increment, i.e. the tick size as a [`Price`].
+    pub fn min_price_increment(&self) -> Price {
+        Price::from_raw(self.min_price_increment)
+    }
+
+    /// Returns the upper price limit as a [`Price`].
+    pub fn high_limit_price(&self) -> Price {
+        Price::from_raw(self.high_limit_price)
+    }
+
+    /// Returns the lower price limit as a [`Price`].
+    pub fn low_limit_price(&self) -> Price {
+        Price::from_raw(self.low_limit_price)
+    }
+
+    /// Returns the maximum price variation as a [`Price`].
+    pub fn max_price_variation(&self) -> Price {
+        Price::from_raw(self.max_price_variation)
+    }
+
+    /// Returns the trading reference price as a [`Price`].
+    pub fn trading_reference_price(&self) -> Price {
+        Price::from_raw(self.trading_reference_price)
+    }
+
+    /// Returns the value of one tick, i.e. the minimum price increment multiplied by
+    /// the contract size, as a [`Price`].
+    pub fn min_price_increment_amount(&self) -> Price {
+        Price::from_raw(self.min_price_increment_amount)
+    }
+
+    /// Parses the raw `security_update_action` into a [`SecurityUpdateAction`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown action.
+    pub fn security_update_action(&self) -> crate::Result<SecurityUpdateAction> {
+        SecurityUpdateAction::try_from(self.security_update_action as u8)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for security update action"))
+    }
+
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the last eligible trade time as a [`Timestamp`].
+    pub fn expiration(&self) -> Timestamp {
+        Timestamp::from_nanos(self.expiration)
+    }
+
+    /// Returns the time of instrument activation as a [`Timestamp`].
+    pub fn activation(&self) -> Timestamp {
+        Timestamp::from_nanos(self.activation)
+    }
+
+    /// Returns the currency used for price fields as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `currency` contains invalid UTF-8.
+    pub fn currency(&self) -> crate::Result<&str> {
+        self.currency.as_str()
+    }
+
+    /// Returns the currency used for settlement as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `settl_currency` contains invalid UTF-8.
+    pub fn settl_currency(&self) -> crate::Result<&str> {
+        self.settl_currency.as_str()
+    }
+
+    /// Returns the strategy type of the spread as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `secsubtype` contains invalid UTF-8.
+    pub fn secsubtype(&self) -> crate::Result<&str> {
+        self.secsubtype.as_str()
+    }
+
+    /// Returns the instrument raw symbol assigned by the publisher as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `symbol` contains invalid UTF-8.
+    pub fn symbol(&self) -> crate::Result<&str> {
+        self.symbol.as_str()
+    }
+
+    /// Returns the security group code of the instrument as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `group` contains invalid UTF-8.
+    pub fn group(&self) -> crate::Result<&str> {
+        self.group.as_str()
+    }
+
+    /// Returns the exchange used to identify the instrument as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `exchange` contains invalid UTF-8.
+    pub fn exchange(&self) -> crate::Result<&str> {
+        self.exchange.as_str()
+    }
+
+    /// Returns the underlying asset code (product code) of the instrument as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `asset` contains invalid UTF-8.
+    pub fn asset(&self) -> crate::Result<&str> {
+        self.asset.as_str()
+    }
+
+    /// Returns the ISO standard instrument categorization code as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `cfi` contains invalid UTF-8.
+    pub fn cfi(&self) -> crate::Result<&str> {
+        self.cfi.as_str()
+    }
+
+    /// Returns the type of the instrument as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `security_type` contains invalid UTF-8.
+    pub fn security_type(&self) -> crate::Result<&str> {
+        self.security_type.as_str()
+    }
+
+    /// Returns the unit of measure for the instrument's original contract size as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `unit_of_measure` contains invalid UTF-8.
+    pub fn unit_of_measure(&self) -> crate::Result<&str> {
+        self.unit_of_measure.as_str()
+    }
+
+    /// Returns the symbol of the first underlying instrument as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `underlying` contains invalid UTF-8.
+    pub fn underlying(&self) -> crate::Result<&str> {
+        self.underlying.as_str()
+    }
+
+    /// Returns the symbol of a related instrument as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `related` contains invalid UTF-8.
+    pub fn related(&self) -> crate::Result<&str> {
+        self.related.as_str()
+    }
+
+    /// Parses the raw `match_algorithm` into a [`MatchAlgorithm`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown algorithm.
+    pub fn match_algorithm(&self) -> crate::Result<MatchAlgorithm> {
+        MatchAlgorithm::try_from(self.match_algorithm as u8)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for match algorithm"))
+    }
+
+    /// Parses the raw `md_security_trading_status` into a [`SecurityTradingStatus`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown status.
+    pub fn md_security_trading_status(&self) -> crate::Result<SecurityTradingStatus> {
+        SecurityTradingStatus::try_from(self.md_security_trading_status)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for security trading status"))
+    }
+
+    /// Returns the settlement price type indicators as a [`SettlPriceType`].
+    pub fn settl_price_type(&self) -> SettlPriceType {
+        SettlPriceType::from_raw(self.settl_price_type)
+    }
+
+    /// Parses the raw `underlying_product` into an [`UnderlyingProduct`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown product complex.
+    pub fn underlying_product(&self) -> crate::Result<UnderlyingProduct> {
+        UnderlyingProduct::try_from(self.underlying_product)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for underlying product"))
+    }
+
+    /// Parses the raw `user_defined_instrument` into a [`UserDefinedInstrument`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field is neither `'Y'` nor `'N'`.
+    pub fn user_defined_instrument(&self) -> crate::Result<UserDefinedInstrument> {
+        UserDefinedInstrument::try_from(self.user_defined_instrument as u8)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for user-defined instrument"))
+    }
+}
+
+impl StatusMsg {
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the security group code as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if `group` contains invalid UTF-8.
+    pub fn group(&self) -> crate::Result<&str> {
+        self.group.as_str()
+    }
+
+    /// Parses the raw `trading_status` into a [`SecurityTradingStatus`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown status.
+    pub fn trading_status(&self) -> crate::Result<SecurityTradingStatus> {
+        SecurityTradingStatus::try_from(self.trading_status)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for trading status"))
+    }
+
+    /// Parses the raw `halt_reason` into a [`HaltReason`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown reason.
+    pub fn halt_reason(&self) -> crate::Result<HaltReason> {
+        HaltReason::try_from(self.halt_reason)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for halt reason"))
+    }
+
+    /// Parses the raw `trading_event` into a [`TradingEvent`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown event.
+    pub fn trading_event(&self) -> crate::Result<TradingEvent> {
+        TradingEvent::try_from(self.trading_event)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for trading event"))
+    }
+}
+
+impl Imbalance {
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the time of the auction as a [`Timestamp`].
+    pub fn auction_time(&self) -> Timestamp {
+        Timestamp::from_nanos(self.auction_time)
+    }
+
+    /// Returns the reference price as a [`Price`].
+    pub fn ref_price(&self) -> Price {
+        Price::from_raw(self.ref_price)
+    }
+
+    /// Returns the continuous book clearing price as a [`Price`].
+    pub fn cont_book_clr_price(&self) -> Price {
+        Price::from_raw(self.cont_book_clr_price)
+    }
+
+    /// Returns the auction interest clearing price as a [`Price`].
+    pub fn auct_interest_clr_price(&self) -> Price {
+        Price::from_raw(self.auct_interest_clr_price)
+    }
+
+    /// Returns the short-selling restriction filling price as a [`Price`].
+    pub fn ssr_filling_price(&self) -> Price {
+        Price::from_raw(self.ssr_filling_price)
+    }
+
+    /// Returns the indicative match price as a [`Price`].
+    pub fn ind_match_price(&self) -> Price {
+        Price::from_raw(self.ind_match_price)
+    }
+
+    /// Returns the upper limit of the price collar as a [`Price`].
+    pub fn upper_collar(&self) -> Price {
+        Price::from_raw(self.upper_collar)
+    }
+
+    /// Returns the lower limit of the price collar as a [`Price`].
+    pub fn lower_collar(&self) -> Price {
+        Price::from_raw(self.lower_collar)
+    }
+
+    /// Parses the raw `auction_type` into an [`AuctionType`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown auction type.
+    pub fn auction_type(&self) -> crate::Result<AuctionType> {
+        AuctionType::try_from(self.auction_type as u8)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for auction type"))
+    }
+
+    /// Parses the raw `side` into a [`Side`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field isn't a valid side.
+    pub fn side(&self) -> crate::Result<Side> {
+        Side::try_from(self.side)
+    }
+
+    /// Parses the raw `unpaired_side` into a [`Side`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field isn't a valid side.
+    pub fn unpaired_side(&self) -> crate::Result<Side> {
+        Side::try_from(self.unpaired_side)
+    }
+
+    /// Parses the raw `auction_status` into an [`AuctionStatus`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown status.
+    pub fn auction_status(&self) -> crate::Result<AuctionStatus> {
+        AuctionStatus::try_from(self.auction_status)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for auction status"))
+    }
+
+    /// Parses the raw `freeze_status` into a [`FreezeStatus`].
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown status.
+    pub fn freeze_status(&self) -> crate::Result<FreezeStatus> {
+        FreezeStatus::try_from(self.freeze_status)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for freeze status"))
+    }
+}
+
+impl StatMsg {
+    /// Returns the capture server received timestamp as a [`Timestamp`].
+    pub fn ts_recv(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_recv)
+    }
+
+    /// Returns the reference timestamp of the statistic as a [`Timestamp`].
+    pub fn ts_ref(&self) -> Timestamp {
+        Timestamp::from_nanos(self.ts_ref)
+    }
+
+    /// Returns the delta of `ts_recv - ts_exchange_send` as a [`Duration`].
+    pub fn ts_in_delta(&self) -> Duration {
+        Duration::from_nanos(self.ts_in_delta as i64)
+    }
+
+    /// Returns the value of a price statistic as a [`Price`].
+    pub fn price(&self) -> Price {
+        Price::from_raw(self.price)
+    }
+
+    /// Parses the type of statistic.
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown statistic type.
+    pub fn stat_type(&self) -> crate::Result<StatType> {
+        StatType::try_from(self.stat_type)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for stat type"))
+    }
+
+    /// Parses the update action of the statistic.
+    ///
+    /// # Errors
+    /// This function returns an error if the field contains an unknown action.
+    pub fn update_action(&self) -> crate::Result<StatUpdateAction> {
+        StatUpdateAction::try_from(self.update_action)
+            .map_err(|_| crate::Error::TypeConversion("Invalid value for stat update action"))
+    }
+}
+
+impl SymbolMappingMsg {
+    /// Returns the input symbol as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if the symbol isn't valid UTF-8.
+    pub fn stype_in_symbol(&self) -> crate::Result<&str> {
+        self.stype_in_symbol.as_str()
+    }
+
+    /// Returns the output symbol as a `&str`.
+    ///
+    /// # Errors
+    /// This function returns an error if the symbol isn't valid UTF-8.
+    pub fn stype_out_symbol(&self) -> crate::Result<&str> {
+        self.stype_out_symbol.as_str()
+    }
+
+    /// Returns the start of the mapping interval as a [`Timestamp`].
+    pub fn start_ts(&self) -> Timestamp {
+        Timestamp::from_nanos(self.start_ts)
+    }
+
+    /// Returns the end of the mapping interval as a [`Timestamp`].
+    pub fn end_ts(&self) -> Timestamp {
+        Timestamp::from_nanos(self.end_ts)
+    }
+}
+
+impl Default for BidAskPair {
+    fn default() -> Self {
+        Self {
+            bid_px: UNDEF_PRICE,
+            ask_px: UNDEF_PRICE,
+            bid_sz: 0,
+            ask_sz: 0,
+            bid_ct: 0,
+            ask_ct: 0,
+        }
+    }
+}
+
+// Defaults use the undefined sentinels for prices and timestamps, `Side::None` for
+// sides, and zero for everything else.
+impl Default for MboMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            order_id: 0,
+            price: UNDEF_PRICE,
+            size: 0,
+            flags: 0,
+            channel_id: 0,
+            action: 0,
+            side: c_char::from(Side::None),
+            ts_recv: UNDEF_TIMESTAMP,
+            ts_in_delta: 0,
+            sequence: 0,
+        }
+    }
+}
+
+impl Default for TradeMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            price: UNDEF_PRICE,
+            size: 0,
+            action: 0,
+            side: c_char::from(Side::None),
+            flags: 0,
+            depth: 0,
+            ts_recv: UNDEF_TIMESTAMP,
+            ts_in_delta: 0,
+            sequence: 0,
+            booklevel: Default::default(),
+        }
+    }
+}
+
+impl Default for Mbp1Msg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            price: UNDEF_PRICE,
+            size: 0,
+            action: 0,
+            side: c_char::from(Side::None),
+            flags: 0,
+            depth: 0,
+            ts_recv: UNDEF_TIMESTAMP,
+            ts_in_delta: 0,
+            sequence: 0,
+            booklevel: Default::default(),
+        }
+    }
+}
+
+impl Default for Mbp10Msg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            price: UNDEF_PRICE,
+            size: 0,
+            action: 0,
+            side: c_char::from(Side::None),
+            flags: 0,
+            depth: 0,
+            ts_recv: UNDEF_TIMESTAMP,
+            ts_in_delta: 0,
+            sequence: 0,
+            booklevel: Default::default(),
+        }
+    }
+}
+
+impl Default for OhlcvMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            open: UNDEF_PRICE,
+            high: UNDEF_PRICE,
+            low: UNDEF_PRICE,
+            close: UNDEF_PRICE,
+            volume: 0,
+        }
+    }
+}
+
+impl Default for StatusMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            ts_recv: UNDEF_TIMESTAMP,
+            group: Default::default(),
+            trading_status: 0,
+            halt_reason: 0,
+            trading_event: 0,
+        }
+    }
+}
+
+impl Default for InstrumentDefMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            ts_recv: UNDEF_TIMESTAMP,
+            min_price_increment: UNDEF_PRICE,
+            display_factor: UNDEF_PRICE,
+            expiration: UNDEF_TIMESTAMP,
+            activation: UNDEF_TIMESTAMP,
+            high_limit_price: UNDEF_PRICE,
+            low_limit_price: UNDEF_PRICE,
+            max_price_variation: UNDEF_PRICE,
+            trading_reference_price: UNDEF_PRICE,
+            unit_of_measure_qty: UNDEF_PRICE,
+            min_price_increment_amount: UNDEF_PRICE,
+            price_ratio: UNDEF_PRICE,
+            inst_attrib_value: 0,
+            underlying_id: 0,
+            cleared_volume: 0,
+            market_depth_implied: 0,
+            market_depth: 0,
+            market_segment_id: 0,
+            max_trade_vol: 0,
+            min_lot_size: 0,
+            min_lot_size_block: 0,
+            min_lot_size_round_lot: 0,
+            min_trade_vol: 0,
+            open_interest_qty: 0,
+            contract_multiplier: 0,
+            decay_quantity: 0,
+            original_contract_size: 0,
+            related_security_id: 0,
+            trading_reference_date: 0,
+            appl_id: 0,
+            maturity_year: 0,
+            decay_start_date: 0,
+            channel_id: 0,
+            currency: Default::default(),
+            settl_currency: Default::default(),
+            secsubtype: Default::default(),
+            symbol: Default::default(),
+            group: Default::default(),
+            exchange: Default::default(),
+            asset: Default::default(),
+            cfi: Default::default(),
+            security_type: Default::default(),
+            unit_of_measure: Default::default(),
+            underlying: Default::default(),
+            related: Default::default(),
+            match_algorithm: 0,
+            md_security_trading_status: 0,
+            main_fraction: 0,
+            price_display_format: 0,
+            settl_price_type: 0,
+            sub_fraction: 0,
+            underlying_product: 0,
+            security_update_action: SecurityUpdateAction::Add as u8 as c_char,
+            maturity_month: 0,
+            maturity_day: 0,
+            maturity_week: 0,
+            user_defined_instrument: 0,
+            contract_multiplier_unit: 0,
+            flow_schedule_type: 0,
+            tick_rule: 0,
+            _dummy: [0; 3],
+        }
+    }
+}
+
+impl Default for Imbalance {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            ts_recv: UNDEF_TIMESTAMP,
+            ref_price: UNDEF_PRICE,
+            auction_time: UNDEF_TIMESTAMP,
+            cont_book_clr_price: UNDEF_PRICE,
+            auct_interest_clr_price: UNDEF_PRICE,
+            ssr_filling_price: UNDEF_PRICE,
+            ind_match_price: UNDEF_PRICE,
+            upper_collar: UNDEF_PRICE,
+            lower_collar: UNDEF_PRICE,
+            paired_qty: 0,
+            total_imbalance_qty: 0,
+            market_imbalance_qty: 0,
+            auction_type: 0,
+            side: c_char::from(Side::None),
+            auction_status: 0,
+            freeze_status: 0,
+            num_extensions: 0,
+            unpaired_qty: 0,
+            unpaired_side: c_char::from(Side::None),
+            significant_imbalance: 0,
+            _dummy: [0; 4],
+        }
+    }
+}
+
+impl Default for GatewayErrorMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            err: Default::default(),
+        }
+    }
+}
+
+impl Default for SymbolMappingMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            stype_in_symbol: Default::default(),
+            stype_out_symbol: Default::default(),
+            _dummy: [0; 4],
+            start_ts: UNDEF_TIMESTAMP,
+            end_ts: UNDEF_TIMESTAMP,
+        }
+    }
+}
+
+impl Default for StatMsg {
+    fn default() -> Self {
+        Self {
+            hd: RecordHeader::new::<Self>(0, 0, UNDEF_TIMESTAMP),
+            ts_recv: UNDEF_TIMESTAMP,
+            ts_ref: UNDEF_TIMESTAMP,
+            price: UNDEF_PRICE,
+            quantity: i32::MAX,
+            sequence: 0,
+            ts_in_delta: 0,
+            stat_type: 0,
+            channel_id: 0,
+            update_action: StatUpdateAction::New as u8,
+            stat_flags: 0,
+            _dummy: [0; 6],
+        }
+    }
+}
+
+macro_rules! impl_record_new {
+    ($($ty:ty),*) => {
+        $(
+            impl $ty {
+                /// Creates a record whose header has the given publisher ID, product ID
+                /// and `ts_event`, and a `length` and `rtype` derived from the record
+                /// type. The other fields are set to their [`Default`] values and can be
+                /// filled in with struct update syntax.
+                pub fn new(publisher_id: u16, product_id: u32, ts_event: u64) -> Self {
+                    Self {
+                        hd: RecordHeader::new::<Self>(publisher_id, product_id, ts_event),
+                        ..Default::default()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_record_new!(
+    MboMsg,
+    TradeMsg,
+    Mbp1Msg,
+    Mbp10Msg,
+    OhlcvMsg,
+    StatusMsg,
+    InstrumentDefMsg,
+    Imbalance,
+    GatewayErrorMsg,
+    SymbolMappingMsg,
+    StatMsg
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const OHLCV_MSG: OhlcvMsg = OhlcvMsg {
-        hd: RecordHeader {
-            length: 56,
-            rtype: 17,
-            publisher_id: 1,
-            product_id: 5482,
-            ts_event: 1609160400000000000,
-        },
-        open: 372025000000000,
-        high: 372050000000000,
-        low: 372025000000000,
-        close: 372050000000000,
-        volume: 57,
-    };
+    use crate::test_utils::{MBO_MSG, OHLCV_MSG};
 
     #[test]
+    #[allow(deprecated)]
     fn test_transmute_record_bytes() {
         unsafe {
             let ohlcv_bytes = std::slice::from_raw_parts(
@@ -603,6 +1852,7 @@ mod tests {
 
     #[test]
     #[should_panic]
+    #[allow(deprecated)]
     fn test_transmute_record_bytes_small_buffer() {
         let source = OHLCV_MSG;
         unsafe {
@@ -615,6 +1865,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_transmute_record() {
         let source = Box::new(OHLCV_MSG);
         let ohlcv_ref: &OhlcvMsg = unsafe { transmute_record(&source.hd) }.unwrap();
@@ -622,6 +1873,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_transmute_record_mut() {
         let mut source = Box::new(OHLCV_MSG);
         let ohlcv_ref: &OhlcvMsg = unsafe { transmute_record_mut(&mut source.hd) }.unwrap();
@@ -632,4 +1884,482 @@ mod tests {
     fn test_symbol_mapping_size() {
         assert_eq!(mem::size_of::<SymbolMappingMsg>(), 80);
     }
+
+    #[test]
+    fn test_record_sizes() {
+        assert_eq!(mem::size_of::<MboMsg>(), 56);
+        assert_eq!(mem::size_of::<TradeMsg>(), 48);
+        assert_eq!(mem::size_of::<Mbp1Msg>(), 80);
+        assert_eq!(mem::size_of::<Mbp10Msg>(), 368);
+        assert_eq!(mem::size_of::<OhlcvMsg>(), 56);
+        assert_eq!(mem::size_of::<StatusMsg>(), 48);
+        assert_eq!(mem::size_of::<InstrumentDefMsg>(), 360);
+        assert_eq!(mem::size_of::<Imbalance>(), 112);
+        assert_eq!(mem::size_of::<GatewayErrorMsg>(), 80);
+        assert_eq!(mem::size_of::<StatMsg>(), 64);
+    }
+
+    /// Asserts the size of a record is the sum of the sizes of its fields, i.e. that
+    /// any padding is covered by explicit `_dummy` fields.
+    macro_rules! assert_no_padding {
+        ($record:expr, $($field:ident),+ $(,)?) => {{
+            let record = $record;
+            assert_eq!(
+                mem::size_of_val(&record),
+                0 $(+ mem::size_of_val(&record.$field))+,
+                "{} contains padding",
+                stringify!($record)
+            );
+        }};
+    }
+
+    #[test]
+    fn test_no_padding() {
+        assert_no_padding!(
+            OHLCV_MSG.hd,
+            length,
+            rtype,
+            publisher_id,
+            product_id,
+            ts_event
+        );
+        assert_no_padding!(
+            MboMsg::default(),
+            hd,
+            order_id,
+            price,
+            size,
+            flags,
+            channel_id,
+            action,
+            side,
+            ts_recv,
+            ts_in_delta,
+            sequence
+        );
+        assert_no_padding!(
+            BidAskPair::default(),
+            bid_px,
+            ask_px,
+            bid_sz,
+            ask_sz,
+            bid_ct,
+            ask_ct
+        );
+        assert_no_padding!(
+            TradeMsg::default(),
+            hd,
+            price,
+            size,
+            action,
+            side,
+            flags,
+            depth,
+            ts_recv,
+            ts_in_delta,
+            sequence,
+            booklevel
+        );
+        assert_no_padding!(
+            Mbp1Msg::default(),
+            hd,
+            price,
+            size,
+            action,
+            side,
+            flags,
+            depth,
+            ts_recv,
+            ts_in_delta,
+            sequence,
+            booklevel
+        );
+        assert_no_padding!(
+            Mbp10Msg::default(),
+            hd,
+            price,
+            size,
+            action,
+            side,
+            flags,
+            depth,
+            ts_recv,
+            ts_in_delta,
+            sequence,
+            booklevel
+        );
+        assert_no_padding!(OhlcvMsg::default(), hd, open, high, low, close, volume);
+        assert_no_padding!(
+            StatusMsg::default(),
+            hd,
+            ts_recv,
+            group,
+            trading_status,
+            halt_reason,
+            trading_event
+        );
+        assert_no_padding!(
+            InstrumentDefMsg::default(),
+            hd,
+            ts_recv,
+            min_price_increment,
+            display_factor,
+            expiration,
+            activation,
+            high_limit_price,
+            low_limit_price,
+            max_price_variation,
+            trading_reference_price,
+            unit_of_measure_qty,
+            min_price_increment_amount,
+            price_ratio,
+            inst_attrib_value,
+            underlying_id,
+            cleared_volume,
+            market_depth_implied,
+            market_depth,
+            market_segment_id,
+            max_trade_vol,
+            min_lot_size,
+            min_lot_size_block,
+            min_lot_size_round_lot,
+            min_trade_vol,
+            open_interest_qty,
+            contract_multiplier,
+            decay_quantity,
+            original_contract_size,
+            related_security_id,
+            trading_reference_date,
+            appl_id,
+            maturity_year,
+            decay_start_date,
+            channel_id,
+            currency,
+            settl_currency,
+            secsubtype,
+            symbol,
+            group,
+            exchange,
+            asset,
+            cfi,
+            security_type,
+            unit_of_measure,
+            underlying,
+            related,
+            match_algorithm,
+            md_security_trading_status,
+            main_fraction,
+            price_display_format,
+            settl_price_type,
+            sub_fraction,
+            underlying_product,
+            security_update_action,
+            maturity_month,
+            maturity_day,
+            maturity_week,
+            user_defined_instrument,
+            contract_multiplier_unit,
+            flow_schedule_type,
+            tick_rule,
+            _dummy
+        );
+        assert_no_padding!(
+            Imbalance::default(),
+            hd,
+            ts_recv,
+            ref_price,
+            auction_time,
+            cont_book_clr_price,
+            auct_interest_clr_price,
+            ssr_filling_price,
+            ind_match_price,
+            upper_collar,
+            lower_collar,
+            paired_qty,
+            total_imbalance_qty,
+            market_imbalance_qty,
+            auction_type,
+            side,
+            auction_status,
+            freeze_status,
+            num_extensions,
+            unpaired_qty,
+            unpaired_side,
+            significant_imbalance,
+            _dummy
+        );
+        assert_no_padding!(GatewayErrorMsg::default(), hd, err);
+        assert_no_padding!(
+            SymbolMappingMsg::default(),
+            hd,
+            stype_in_symbol,
+            stype_out_symbol,
+            _dummy,
+            start_ts,
+            end_ts
+        );
+        assert_no_padding!(
+            StatMsg::default(),
+            hd,
+            ts_recv,
+            ts_ref,
+            price,
+            quantity,
+            sequence,
+            ts_in_delta,
+            stat_type,
+            channel_id,
+            update_action,
+            stat_flags,
+            _dummy
+        );
+    }
+
+    #[test]
+    fn test_new_and_default() {
+        assert_eq!(OHLCV_MSG.hd.length as usize * 4, mem::size_of::<OhlcvMsg>());
+        let mbp10 = Mbp10Msg::new(1, 5482, 1658441851000000000);
+        assert_eq!(mbp10.hd.length as usize * 4, mem::size_of::<Mbp10Msg>());
+        assert_eq!(mbp10.hd.rtype, Mbp10Msg::TYPE_ID);
+        assert_eq!(mbp10.hd.product_id, 5482);
+        assert!(mbp10.price().is_undefined());
+        assert!(mbp10.ts_recv().is_undefined());
+        assert_eq!(mbp10.side().unwrap(), Side::None);
+        assert!(mbp10
+            .booklevel
+            .iter()
+            .all(|level| level.bid_px == UNDEF_PRICE));
+        let trade = TradeMsg {
+            price: 3720250000000,
+            size: 5,
+            ..TradeMsg::new(1, 5482, 1658441851000000000)
+        };
+        assert_eq!(trade.price().to_string(), "3720.250000000");
+        assert_eq!(trade.hd.rtype, TradeMsg::TYPE_ID);
+        let def = InstrumentDefMsg::default();
+        assert_eq!(
+            def.hd.length as usize * 4,
+            mem::size_of::<InstrumentDefMsg>()
+        );
+        assert!(def.hd.ts_event().is_undefined());
+        assert!(def.expiration().is_undefined());
+        assert_eq!(def.symbol().unwrap(), "");
+        let stat = StatMsg::default();
+        assert_eq!(stat.update_action().unwrap(), StatUpdateAction::New);
+        assert_eq!(stat.quantity, i32::MAX);
+    }
+
+    #[test]
+    fn test_stat_accessors() {
+        let mut stat = StatMsg {
+            hd: RecordHeader::new::<StatMsg>(1, 5482, 1658441851000000000),
+            ts_recv: 1658441891000000000,
+            ts_ref: crate::timestamp::UNDEF_TIMESTAMP,
+            price: 3722750000000,
+            quantity: i32::MAX,
+            sequence: 1170352,
+            ts_in_delta: 22993,
+            stat_type: StatType::SettlementPrice as u16,
+            channel_id: 0,
+            update_action: StatUpdateAction::New as u8,
+            stat_flags: 0,
+            _dummy: [0; 6],
+        };
+        assert_eq!(stat.stat_type().unwrap(), StatType::SettlementPrice);
+        assert_eq!(stat.update_action().unwrap(), StatUpdateAction::New);
+        assert_eq!(stat.price().to_string(), "3722.750000000");
+        assert!(stat.ts_ref().is_undefined());
+        stat.stat_type = 0;
+        assert!(stat.stat_type().is_err());
+        stat.update_action = 3;
+        assert!(stat.update_action().is_err());
+    }
+
+    #[test]
+    fn test_status_accessors() {
+        let mut status = StatusMsg {
+            group: FixedStr::try_from("ES").unwrap(),
+            trading_status: 2,
+            halt_reason: 1,
+            trading_event: 0,
+            ..Default::default()
+        };
+        assert_eq!(status.group().unwrap(), "ES");
+        assert_eq!(
+            status.trading_status().unwrap(),
+            SecurityTradingStatus::TradingHalt
+        );
+        assert_eq!(
+            status.halt_reason().unwrap(),
+            HaltReason::SurveillanceIntervention
+        );
+        assert_eq!(status.trading_event().unwrap(), TradingEvent::NoEvent);
+        status.trading_status = 0;
+        assert!(status.trading_status().is_err());
+        status.halt_reason = 7;
+        assert!(status.halt_reason().is_err());
+        status.trading_event = 3;
+        assert!(status.trading_event().is_err());
+    }
+
+    #[test]
+    fn test_imbalance_accessors() {
+        let mut imbalance = Imbalance {
+            ref_price: 172_500_000_000,
+            ind_match_price: 172_510_000_000,
+            auction_type: b'C' as c_char,
+            side: b'B' as c_char,
+            unpaired_side: b'N' as c_char,
+            auction_status: 0,
+            freeze_status: 1,
+            ..Default::default()
+        };
+        assert_eq!(imbalance.ref_price().to_string(), "172.500000000");
+        assert_eq!(
+            imbalance.ind_match_price(),
+            Price::from_raw(172_510_000_000)
+        );
+        assert_eq!(imbalance.auction_type().unwrap(), AuctionType::Closing);
+        assert_eq!(imbalance.side().unwrap(), Side::Bid);
+        assert_eq!(imbalance.unpaired_side().unwrap(), Side::None);
+        assert_eq!(imbalance.auction_status().unwrap(), AuctionStatus::WillRun);
+        assert_eq!(imbalance.freeze_status().unwrap(), FreezeStatus::Frozen);
+        imbalance.auction_type = b'X' as c_char;
+        assert!(imbalance.auction_type().is_err());
+        imbalance.side = 0;
+        assert!(imbalance.side().is_err());
+        imbalance.freeze_status = 2;
+        assert!(imbalance.freeze_status().is_err());
+    }
+
+    #[test]
+    fn test_instrument_def_accessors() {
+        let mut def = InstrumentDefMsg {
+            currency: FixedStr::try_from("USD").unwrap(),
+            symbol: FixedStr::try_from("ESZ2").unwrap(),
+            cfi: FixedStr::try_from("FFICSX").unwrap(),
+            expiration: 1671197400000000000,
+            match_algorithm: b'F' as c_char,
+            md_security_trading_status: 17,
+            settl_price_type: 0b11,
+            underlying_product: 5,
+            user_defined_instrument: b'N' as c_char,
+            ..Default::default()
+        };
+        assert_eq!(def.currency().unwrap(), "USD");
+        assert_eq!(def.symbol().unwrap(), "ESZ2");
+        assert_eq!(def.cfi().unwrap(), "FFICSX");
+        assert_eq!(def.exchange().unwrap(), "");
+        assert_eq!(
+            def.expiration().to_string(),
+            "2022-12-16T13:30:00.000000000Z"
+        );
+        assert_eq!(def.match_algorithm().unwrap(), MatchAlgorithm::Fifo);
+        assert_eq!(
+            def.md_security_trading_status().unwrap(),
+            SecurityTradingStatus::ReadyToTrade
+        );
+        assert!(def.settl_price_type().is_final());
+        assert!(def.settl_price_type().is_actual());
+        assert!(!def.settl_price_type().is_intraday());
+        assert_eq!(def.underlying_product().unwrap(), UnderlyingProduct::Equity);
+        assert_eq!(
+            def.user_defined_instrument().unwrap(),
+            UserDefinedInstrument::No
+        );
+        let mut symbol = *def.symbol.raw();
+        symbol[4] = 0xFF_u8 as c_char;
+        def.symbol = FixedStr::from_raw(symbol);
+        assert!(def.symbol().is_err());
+        def.match_algorithm = 0;
+        assert!(def.match_algorithm().is_err());
+        def.md_security_trading_status = 255;
+        assert!(def.md_security_trading_status().is_err());
+        def.underlying_product = 1;
+        assert!(def.underlying_product().is_err());
+        def.user_defined_instrument = b'y' as c_char;
+        assert!(def.user_defined_instrument().is_err());
+    }
+
+    #[test]
+    fn test_action_side_accessors() {
+        let mut mbo = MBO_MSG;
+        assert_eq!(mbo.action().unwrap(), Action::Cancel);
+        assert_eq!(mbo.side().unwrap(), Side::Ask);
+        mbo.side = 0;
+        assert!(mbo.side().is_err());
+    }
+
+    #[test]
+    fn test_price_accessors() {
+        assert_eq!(OHLCV_MSG.open().to_string(), "372025.000000000");
+        assert_eq!(OHLCV_MSG.high(), Price::from_raw(OHLCV_MSG.high));
+    }
+
+    #[cfg(feature = "serde")]
+    fn assert_json_round_trip<T>(record: &T)
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let json = serde_json::to_string(record).unwrap();
+        let res: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(res, *record);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        assert_json_round_trip(&OHLCV_MSG);
+
+        assert_json_round_trip(&MboMsg {
+            order_id: 647784973705,
+            price: 3722750000000,
+            action: 'C' as c_char,
+            side: 'A' as c_char,
+            ts_recv: 1658441891000000000,
+            ..MboMsg::new(1, 5482, u64::MAX - 1)
+        });
+
+        let mut mbp10 = Mbp10Msg::default();
+        for (i, level) in mbp10.booklevel.iter_mut().enumerate() {
+            level.bid_px = 100 - i as i64;
+            level.ask_px = 101 + i as i64;
+            level.bid_sz = i as u32;
+        }
+        assert_json_round_trip(&mbp10);
+
+        assert_json_round_trip(&InstrumentDefMsg {
+            expiration: 1670025600000000000,
+            currency: FixedStr::try_from("USD").unwrap(),
+            symbol: FixedStr::try_from("ESZ2").unwrap(),
+            ..Default::default()
+        });
+
+        assert_json_round_trip(&StatMsg {
+            ts_ref: u64::MAX,
+            price: 3722750000000,
+            stat_type: StatType::OpenInterest as u16,
+            quantity: 1024,
+            ..Default::default()
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_large_u64_from_int() {
+        let json = serde_json::to_string(&OHLCV_MSG)
+            .unwrap()
+            .replace("\"1609160400000000000\"", "1609160400000000000");
+        let res: OhlcvMsg = serde_json::from_str(&json).unwrap();
+        assert_eq!(res.hd.ts_event, OHLCV_MSG.hd.ts_event);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_c_char_arr_too_long() {
+        let mut json: serde_json::Value =
+            serde_json::to_value(InstrumentDefMsg::default()).unwrap();
+        json["currency"] = "USDT".into();
+        assert!(serde_json::from_value::<InstrumentDefMsg>(json).is_err());
+    }
 }
@@ -0,0 +1,263 @@
+//! Enums for working with records whose type is only known at runtime.
+use crate::{
+    record::{
+        ConstTypeId, GatewayErrorMsg, Imbalance, InstrumentDefMsg, MboMsg, Mbp10Msg, Mbp1Msg,
+        OhlcvMsg, RecordHeader, StatMsg, StatusMsg, SymbolMappingMsg, TradeMsg,
+    },
+    Error, RecordRef, Result, Timestamp,
+};
+
+/// An owned record of any type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "trivial_copy", derive(Copy))]
+#[allow(clippy::large_enum_variant)] // avoid boxing to keep records contiguous
+pub enum RecordEnum {
+    /// A market-by-order message.
+    Mbo(MboMsg),
+    /// A trade message.
+    Trade(TradeMsg),
+    /// A market-by-price message with a book depth of 1.
+    Mbp1(Mbp1Msg),
+    /// A market-by-price message with a book depth of 10.
+    Mbp10(Mbp10Msg),
+    /// An open, high, low, close, and volume message.
+    Ohlcv(OhlcvMsg),
+    /// A trading status message.
+    Status(StatusMsg),
+    /// An instrument definition message.
+    InstrumentDef(InstrumentDefMsg),
+    /// An auction imbalance message.
+    Imbalance(Imbalance),
+    /// An error message from the gateway.
+    Error(GatewayErrorMsg),
+    /// A symbol mapping message.
+    SymbolMapping(SymbolMappingMsg),
+    /// A statistics message.
+    Stat(StatMsg),
+}
+
+/// A reference to a record of any type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordRefEnum<'a> {
+    /// A reference to a market-by-order message.
+    Mbo(&'a MboMsg),
+    /// A reference to a trade message.
+    Trade(&'a TradeMsg),
+    /// A reference to a market-by-price message with a book depth of 1.
+    Mbp1(&'a Mbp1Msg),
+    /// A reference to a market-by-price message with a book depth of 10.
+    Mbp10(&'a Mbp10Msg),
+    /// A reference to an open, high, low, close, and volume message.
+    Ohlcv(&'a OhlcvMsg),
+    /// A reference to a trading status message.
+    Status(&'a StatusMsg),
+    /// A reference to an instrument definition message.
+    InstrumentDef(&'a InstrumentDefMsg),
+    /// A reference to an auction imbalance message.
+    Imbalance(&'a Imbalance),
+    /// A reference to an error message from the gateway.
+    Error(&'a GatewayErrorMsg),
+    /// A reference to a symbol mapping message.
+    SymbolMapping(&'a SymbolMappingMsg),
+    /// A reference to a statistics message.
+    Stat(&'a StatMsg),
+}
+
+/// Calls `$body` with `$rec` bound to the record inside any variant of `$enum`.
+macro_rules! visit_record {
+    ($enum:ident, $value:expr, $rec:ident => $body:expr) => {
+        match $value {
+            $enum::Mbo($rec) => $body,
+            $enum::Trade($rec) => $body,
+            $enum::Mbp1($rec) => $body,
+            $enum::Mbp10($rec) => $body,
+            $enum::Ohlcv($rec) => $body,
+            $enum::Status($rec) => $body,
+            $enum::InstrumentDef($rec) => $body,
+            $enum::Imbalance($rec) => $body,
+            $enum::Error($rec) => $body,
+            $enum::SymbolMapping($rec) => $body,
+            $enum::Stat($rec) => $body,
+        }
+    };
+}
+
+/// Returns the capture server received timestamp of `$value` if its record type
+/// has one.
+macro_rules! ts_recv {
+    ($enum:ident, $value:expr) => {
+        match $value {
+            $enum::Mbo(rec) => Some(rec.ts_recv()),
+            $enum::Trade(rec) => Some(rec.ts_recv()),
+            $enum::Mbp1(rec) => Some(rec.ts_recv()),
+            $enum::Mbp10(rec) => Some(rec.ts_recv()),
+            $enum::Status(rec) => Some(rec.ts_recv()),
+            $enum::InstrumentDef(rec) => Some(rec.ts_recv()),
+            $enum::Imbalance(rec) => Some(rec.ts_recv()),
+            $enum::Stat(rec) => Some(rec.ts_recv()),
+            $enum::Ohlcv(_) | $enum::Error(_) | $enum::SymbolMapping(_) => None,
+        }
+    };
+}
+
+impl RecordEnum {
+    /// Returns a reference to the common header of the record.
+    pub fn header(&self) -> &RecordHeader {
+        visit_record!(RecordEnum, self, rec => &rec.hd)
+    }
+
+    /// Returns the matching engine received timestamp of the record.
+    pub fn ts_event(&self) -> Timestamp {
+        self.header().ts_event()
+    }
+
+    /// Returns the capture server received timestamp of the record, if the record type
+    /// has one.
+    pub fn ts_recv(&self) -> Option<Timestamp> {
+        ts_recv!(RecordEnum, self)
+    }
+
+    /// Returns a [`RecordRefEnum`] referencing this record.
+    pub fn as_ref_enum(&self) -> RecordRefEnum<'_> {
+        visit_record!(RecordEnum, self, rec => RecordRefEnum::from(rec))
+    }
+
+    /// Returns a [`RecordRef`] referencing this record.
+    pub fn as_record_ref(&self) -> RecordRef<'_> {
+        visit_record!(RecordEnum, self, rec => RecordRef::from(rec))
+    }
+}
+
+impl<'a> RecordRefEnum<'a> {
+    /// Creates a reference to the record at the beginning of `bytes`.
+    ///
+    /// # Errors
+    /// This function returns an error if `bytes` doesn't contain a valid record or
+    /// the record's `rtype` isn't recognized.
+    pub fn try_from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        Self::try_from(RecordRef::try_from_bytes(bytes)?)
+    }
+
+    /// Returns a reference to the common header of the record.
+    pub fn header(&self) -> &'a RecordHeader {
+        visit_record!(RecordRefEnum, *self, rec => &rec.hd)
+    }
+
+    /// Returns the matching engine received timestamp of the record.
+    pub fn ts_event(&self) -> Timestamp {
+        self.header().ts_event()
+    }
+
+    /// Returns the capture server received timestamp of the record, if the record type
+    /// has one.
+    pub fn ts_recv(&self) -> Option<Timestamp> {
+        ts_recv!(RecordRefEnum, *self)
+    }
+
+    /// Returns a [`RecordRef`] referencing the same record.
+    pub fn as_record_ref(&self) -> RecordRef<'a> {
+        visit_record!(RecordRefEnum, *self, rec => RecordRef::from(rec))
+    }
+}
+
+impl<'a> TryFrom<RecordRef<'a>> for RecordRefEnum<'a> {
+    type Error = Error;
+
+    fn try_from(rec_ref: RecordRef<'a>) -> Result<Self> {
+        Ok(match rec_ref.header().rtype {
+            MboMsg::TYPE_ID => Self::Mbo(rec_ref.try_get()?),
+            TradeMsg::TYPE_ID => Self::Trade(rec_ref.try_get()?),
+            Mbp1Msg::TYPE_ID => Self::Mbp1(rec_ref.try_get()?),
+            Mbp10Msg::TYPE_ID => Self::Mbp10(rec_ref.try_get()?),
+            OhlcvMsg::TYPE_ID => Self::Ohlcv(rec_ref.try_get()?),
+            StatusMsg::TYPE_ID => Self::Status(rec_ref.try_get()?),
+            InstrumentDefMsg::TYPE_ID => Self::InstrumentDef(rec_ref.try_get()?),
+            Imbalance::TYPE_ID => Self::Imbalance(rec_ref.try_get()?),
+            GatewayErrorMsg::TYPE_ID => Self::Error(rec_ref.try_get()?),
+            SymbolMappingMsg::TYPE_ID => Self::SymbolMapping(rec_ref.try_get()?),
+            StatMsg::TYPE_ID => Self::Stat(rec_ref.try_get()?),
+            rtype => return Err(Error::UnknownRType(rtype)),
+        })
+    }
+}
+
+impl<'a> From<&'a RecordEnum> for RecordRefEnum<'a> {
+    fn from(rec: &'a RecordEnum) -> Self {
+        rec.as_ref_enum()
+    }
+}
+
+impl From<RecordRefEnum<'_>> for RecordEnum {
+    // Records are only `Copy` with the `trivial_copy` feature
+    #[allow(clippy::clone_on_copy)]
+    fn from(rec_ref: RecordRefEnum) -> Self {
+        visit_record!(RecordRefEnum, rec_ref, rec => RecordEnum::from(rec.clone()))
+    }
+}
+
+macro_rules! impl_from_record {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        $(
+            impl From<$ty> for RecordEnum {
+                fn from(rec: $ty) -> Self {
+                    Self::$variant(rec)
+                }
+            }
+
+            impl<'a> From<&'a $ty> for RecordRefEnum<'a> {
+                fn from(rec: &'a $ty) -> Self {
+                    Self::$variant(rec)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_record!(
+    Mbo(MboMsg),
+    Trade(TradeMsg),
+    Mbp1(Mbp1Msg),
+    Mbp10(Mbp10Msg),
+    Ohlcv(OhlcvMsg),
+    Status(StatusMsg),
+    InstrumentDef(InstrumentDefMsg),
+    Imbalance(Imbalance),
+    Error(GatewayErrorMsg),
+    SymbolMapping(SymbolMappingMsg),
+    Stat(StatMsg),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MBO_MSG;
+
+    #[test]
+    fn test_ref_enum_from_bytes() {
+        let bytes = RecordRef::from(&MBO_MSG).as_bytes();
+        let rec = RecordRefEnum::try_from_bytes(bytes).unwrap();
+        assert_eq!(rec, RecordRefEnum::Mbo(&MBO_MSG));
+        assert_eq!(*rec.header(), MBO_MSG.hd);
+        assert_eq!(rec.ts_event(), MBO_MSG.hd.ts_event());
+        assert_eq!(rec.ts_recv(), Some(MBO_MSG.ts_recv()));
+        assert_eq!(RecordEnum::from(rec), RecordEnum::Mbo(MBO_MSG));
+    }
+
+    #[test]
+    fn test_unknown_rtype() {
+        let mut mbo = MBO_MSG;
+        mbo.hd.rtype = 0xFF;
+        let res = RecordRefEnum::try_from(RecordRef::from(&mbo));
+        assert!(matches!(res, Err(Error::UnknownRType(0xFF))));
+    }
+
+    #[test]
+    fn test_owned_round_trip() {
+        let rec = RecordEnum::from(MBO_MSG);
+        assert_eq!(rec.as_ref_enum(), RecordRefEnum::Mbo(&MBO_MSG));
+        assert_eq!(
+            RecordRefEnum::try_from(rec.as_record_ref()).unwrap(),
+            rec.as_ref_enum()
+        );
+    }
+}
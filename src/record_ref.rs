@@ -0,0 +1,199 @@
+//! A safe, zero-copy view of a record of any type.
+use std::{mem, slice};
+
+use crate::{
+    record::{ConstTypeId, RecordHeader},
+    Error, Result,
+};
+
+/// A borrowed view of a single record whose type is only known at runtime.
+///
+/// The length and alignment of the underlying bytes are validated when the view is
+/// created, and `rtype` is validated before converting it to a concrete record type,
+/// so no `unsafe` is required to read records from a buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordRef<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> RecordRef<'a> {
+    /// Creates a view of the record at the beginning of `bytes`. Any bytes past the
+    /// length indicated by the record's header are ignored.
+    ///
+    /// # Errors
+    /// This function returns an error if `bytes` isn't aligned to a [`RecordHeader`],
+    /// is too short to contain a [`RecordHeader`], or is shorter than the length
+    /// specified in the header.
+    pub fn try_from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        const HEADER_SIZE: usize = mem::size_of::<RecordHeader>();
+
+        if bytes.as_ptr().align_offset(mem::align_of::<RecordHeader>()) != 0 {
+            return Err(Error::Decode(format!(
+                "Record bytes must be aligned to {} bytes",
+                mem::align_of::<RecordHeader>()
+            )));
+        }
+        if bytes.len() < HEADER_SIZE {
+            return Err(Error::Decode(format!(
+                "Expected at least {HEADER_SIZE} bytes for a record header, found {}",
+                bytes.len()
+            )));
+        }
+        // Safety: `bytes` is aligned and long enough to contain a `RecordHeader`, which is
+        // valid for any bit pattern.
+        let header = unsafe { &*(bytes.as_ptr() as *const RecordHeader) };
+        let record_size = header.length as usize * 4;
+        if record_size < HEADER_SIZE {
+            return Err(Error::Decode(format!(
+                "Record length of {record_size} bytes is shorter than a record header"
+            )));
+        }
+        if record_size > bytes.len() {
+            return Err(Error::Decode(format!(
+                "Record length of {record_size} bytes exceeds the {} bytes available",
+                bytes.len()
+            )));
+        }
+        Ok(Self {
+            bytes: &bytes[..record_size],
+        })
+    }
+
+    /// Returns a reference to the common header of the record.
+    pub fn header(&self) -> &'a RecordHeader {
+        // Safety: validated on construction.
+        unsafe { &*(self.bytes.as_ptr() as *const RecordHeader) }
+    }
+
+    /// Returns the size of the record in bytes.
+    pub fn record_size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns the raw bytes of the record.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns `true` if the record is of type `T`.
+    pub fn has<T: ConstTypeId>(&self) -> bool {
+        self.header().rtype == T::TYPE_ID && self.bytes.len() >= mem::size_of::<T>()
+    }
+
+    /// Returns a reference to the record as a `T` if it's of type `T`, otherwise
+    /// `None`.
+    pub fn get<T: ConstTypeId>(&self) -> Option<&'a T> {
+        if self.has::<T>() {
+            // Safety: `ConstTypeId` guarantees `T` is valid for any bit pattern and
+            // doesn't require greater alignment than `RecordHeader`, and `rtype` and
+            // the length have been checked.
+            Some(unsafe { &*(self.bytes.as_ptr() as *const T) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the record as a `T`.
+    ///
+    /// # Errors
+    /// This function returns an error if the record's `rtype` doesn't match
+    /// `T::TYPE_ID` or the record is too short to be a `T`.
+    pub fn try_get<T: ConstTypeId>(&self) -> Result<&'a T> {
+        let rtype = self.header().rtype;
+        if rtype != T::TYPE_ID {
+            return Err(Error::Decode(format!(
+                "Expected rtype {:#04X}, found {rtype:#04X}",
+                T::TYPE_ID
+            )));
+        }
+        self.get().ok_or_else(|| {
+            Error::Decode(format!(
+                "Record length of {} bytes is shorter than the expected {} bytes",
+                self.bytes.len(),
+                mem::size_of::<T>()
+            ))
+        })
+    }
+}
+
+impl<'a, T: ConstTypeId> From<&'a T> for RecordRef<'a> {
+    fn from(record: &'a T) -> Self {
+        Self {
+            // Safety: the contract of `ConstTypeId` requires implementing types to contain
+            // no padding, so every byte is initialized.
+            bytes: unsafe {
+                slice::from_raw_parts(record as *const T as *const u8, mem::size_of::<T>())
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        record::{MboMsg, OhlcvMsg},
+        test_utils::OHLCV_MSG,
+    };
+
+    /// Copies `bytes` into a buffer with the given offset from 8-byte alignment.
+    fn copy_with_offset(bytes: &[u8], offset: usize, buffer: &mut Vec<u8>) -> usize {
+        buffer.resize(bytes.len() + 16, 0);
+        let start = buffer.as_ptr().align_offset(8) + offset;
+        buffer[start..start + bytes.len()].copy_from_slice(bytes);
+        start
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let mut buffer = Vec::new();
+        let bytes = RecordRef::from(&OHLCV_MSG).as_bytes();
+        let start = copy_with_offset(bytes, 0, &mut buffer);
+        let rec_ref = RecordRef::try_from_bytes(&buffer[start..]).unwrap();
+        assert_eq!(rec_ref.record_size(), mem::size_of::<OhlcvMsg>());
+        assert_eq!(*rec_ref.header(), OHLCV_MSG.hd);
+        assert!(rec_ref.has::<OhlcvMsg>());
+        assert!(!rec_ref.has::<MboMsg>());
+        assert_eq!(*rec_ref.get::<OhlcvMsg>().unwrap(), OHLCV_MSG);
+        assert!(rec_ref.get::<MboMsg>().is_none());
+        assert!(matches!(rec_ref.try_get::<MboMsg>(), Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn test_try_from_bytes_small_buffer() {
+        let bytes = RecordRef::from(&OHLCV_MSG).as_bytes();
+        assert!(matches!(
+            RecordRef::try_from_bytes(&bytes[..bytes.len() - 8]),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            RecordRef::try_from_bytes(&bytes[..8]),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_from_bytes_misaligned() {
+        let mut buffer = Vec::new();
+        let bytes = RecordRef::from(&OHLCV_MSG).as_bytes();
+        let start = copy_with_offset(bytes, 1, &mut buffer);
+        assert!(matches!(
+            RecordRef::try_from_bytes(&buffer[start..]),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_length_shorter_than_type() {
+        let mut ohlcv = OHLCV_MSG;
+        ohlcv.hd.length = 8;
+        let bytes = RecordRef::from(&ohlcv).as_bytes();
+        let rec_ref = RecordRef::try_from_bytes(bytes).unwrap();
+        assert_eq!(rec_ref.record_size(), 32);
+        assert!(rec_ref.get::<OhlcvMsg>().is_none());
+        assert!(matches!(
+            rec_ref.try_get::<OhlcvMsg>(),
+            Err(Error::Decode(_))
+        ));
+    }
+}
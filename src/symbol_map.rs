@@ -0,0 +1,317 @@
+//! Time-ranged mappings between symbols and product IDs.
+use std::collections::HashMap;
+
+use crate::{record::SymbolMappingMsg, Error, Result, Timestamp};
+
+/// A lookup between the [`stype_in_symbol`](SymbolMappingMsg::stype_in_symbol) and
+/// [`product_id`](crate::record::RecordHeader::product_id) of [`SymbolMappingMsg`]
+/// records, in both directions, at a point in time.
+///
+/// Each mapping is valid from its `start_ts` up to but excluding its `end_ts`. When a
+/// mapping overlaps an earlier one for the same symbol or product ID, the later mapping
+/// takes precedence for the overlapping period, so product IDs that are reused for a
+/// different symbol on a later date resolve to the symbol for that date. Adjacent
+/// mappings between the same symbol and product ID are combined.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymbolMap {
+    symbols: HashMap<u32, Vec<Interval<String>>>,
+    product_ids: HashMap<String, Vec<Interval<u32>>>,
+}
+
+/// A value valid over the half-open interval `[start, end)` in nanoseconds since the
+/// UNIX epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Interval<T> {
+    start: u64,
+    end: u64,
+    value: T,
+}
+
+impl SymbolMap {
+    /// Creates a new empty symbol map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the map contains no mappings.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Removes all mappings.
+    pub fn clear(&mut self) {
+        self.symbols.clear();
+        self.product_ids.clear();
+    }
+
+    /// Adds the mapping from a symbol mapping record, replacing any existing mappings
+    /// for its symbol or product ID during the interval of the record.
+    ///
+    /// # Errors
+    /// This function returns an error if the input symbol isn't valid UTF-8 or if the
+    /// record's `end_ts` is before its `start_ts`.
+    pub fn insert(&mut self, mapping: &SymbolMappingMsg) -> Result<()> {
+        let symbol = mapping.stype_in_symbol()?;
+        if mapping.end_ts < mapping.start_ts {
+            return Err(Error::TypeConversion(
+                "Symbol mapping ends before it starts",
+            ));
+        }
+        self.add(
+            mapping.hd.product_id,
+            symbol,
+            mapping.start_ts,
+            mapping.end_ts,
+        );
+        Ok(())
+    }
+
+    /// Returns the symbol for `product_id` at `ts`.
+    pub fn symbol(&self, product_id: u32, ts: Timestamp) -> Option<&str> {
+        find(self.symbols.get(&product_id)?, ts.nanos()).map(String::as_str)
+    }
+
+    /// Returns the product ID for `symbol` at `ts`.
+    pub fn product_id(&self, symbol: &str, ts: Timestamp) -> Option<u32> {
+        find(self.product_ids.get(symbol)?, ts.nanos()).copied()
+    }
+
+    fn add(&mut self, product_id: u32, symbol: &str, start: u64, end: u64) {
+        if start == end {
+            return;
+        }
+        // Remove the replaced mappings from the other direction first
+        for (old_symbol, old_start, old_end) in
+            overlapping(self.symbols.get(&product_id), start, end)
+        {
+            remove_from(
+                &mut self.product_ids,
+                &old_symbol,
+                old_start,
+                old_end,
+                |id| *id == product_id,
+            );
+        }
+        for (old_id, old_start, old_end) in overlapping(self.product_ids.get(symbol), start, end) {
+            remove_from(&mut self.symbols, &old_id, old_start, old_end, |sym| {
+                sym == symbol
+            });
+        }
+        set(
+            self.symbols.entry(product_id).or_default(),
+            start,
+            end,
+            symbol.to_owned(),
+        );
+        set(
+            self.product_ids.entry(symbol.to_owned()).or_default(),
+            start,
+            end,
+            product_id,
+        );
+    }
+}
+
+/// Returns the values of `intervals` that overlap `[start, end)` along with the bounds
+/// of the overlap.
+fn overlapping<T: Clone>(
+    intervals: Option<&Vec<Interval<T>>>,
+    start: u64,
+    end: u64,
+) -> Vec<(T, u64, u64)> {
+    intervals
+        .into_iter()
+        .flatten()
+        .filter(|iv| iv.start < end && start < iv.end)
+        .map(|iv| (iv.value.clone(), iv.start.max(start), iv.end.min(end)))
+        .collect()
+}
+
+/// Removes `[start, end)` from the intervals for `key` whose value matches `pred`,
+/// removing `key` if it no longer has any intervals.
+fn remove_from<K, T>(
+    map: &mut HashMap<K, Vec<Interval<T>>>,
+    key: &K,
+    start: u64,
+    end: u64,
+    pred: impl Fn(&T) -> bool,
+) where
+    K: std::hash::Hash + Eq,
+    T: Clone,
+{
+    if let Some(intervals) = map.get_mut(key) {
+        remove_range(intervals, start, end, pred);
+        if intervals.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+/// Removes `[start, end)` from the intervals whose value matches `pred`, splitting
+/// intervals that extend beyond either end. Preserves the ordering of `intervals`.
+fn remove_range<T: Clone>(
+    intervals: &mut Vec<Interval<T>>,
+    start: u64,
+    end: u64,
+    pred: impl Fn(&T) -> bool,
+) {
+    let mut res = Vec::with_capacity(intervals.len() + 1);
+    for iv in intervals.drain(..) {
+        if iv.end <= start || end <= iv.start || !pred(&iv.value) {
+            res.push(iv);
+            continue;
+        }
+        if iv.start < start {
+            res.push(Interval {
+                start: iv.start,
+                end: start,
+                value: iv.value.clone(),
+            });
+        }
+        if end < iv.end {
+            res.push(Interval {
+                start: end,
+                end: iv.end,
+                value: iv.value,
+            });
+        }
+    }
+    *intervals = res;
+}
+
+/// Sets the value of `intervals` to `value` for `[start, end)`, combining it with
+/// adjacent intervals of the same value.
+fn set<T: Clone + PartialEq>(intervals: &mut Vec<Interval<T>>, start: u64, end: u64, value: T) {
+    remove_range(intervals, start, end, |_| true);
+    let mut idx = intervals.partition_point(|iv| iv.start < start);
+    intervals.insert(idx, Interval { start, end, value });
+    if idx + 1 < intervals.len()
+        && intervals[idx].end == intervals[idx + 1].start
+        && intervals[idx].value == intervals[idx + 1].value
+    {
+        intervals[idx].end = intervals.remove(idx + 1).end;
+    }
+    if idx > 0
+        && intervals[idx - 1].end == intervals[idx].start
+        && intervals[idx - 1].value == intervals[idx].value
+    {
+        idx -= 1;
+        intervals[idx].end = intervals.remove(idx + 1).end;
+    }
+}
+
+/// Returns the value of the interval in sorted `intervals` containing `ts`.
+fn find<T>(intervals: &[Interval<T>], ts: u64) -> Option<&T> {
+    let idx = intervals.partition_point(|iv| iv.start <= ts);
+    let iv = intervals.get(idx.checked_sub(1)?)?;
+    (ts < iv.end).then_some(&iv.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{record::RecordHeader, FixedStr};
+
+    fn date(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    fn mapping(product_id: u32, symbol: &str, start: &str, end: &str) -> SymbolMappingMsg {
+        SymbolMappingMsg {
+            hd: RecordHeader::new::<SymbolMappingMsg>(1, product_id, date(start).nanos()),
+            stype_in_symbol: FixedStr::try_from(symbol).unwrap(),
+            stype_out_symbol: FixedStr::default(),
+            _dummy: [0; 4],
+            start_ts: date(start).nanos(),
+            end_ts: date(end).nanos(),
+        }
+    }
+
+    fn symbol_map(mappings: &[SymbolMappingMsg]) -> SymbolMap {
+        let mut symbol_map = SymbolMap::new();
+        for mapping in mappings {
+            symbol_map.insert(mapping).unwrap();
+        }
+        symbol_map
+    }
+
+    #[test]
+    fn test_lookup() {
+        let symbol_map = symbol_map(&[
+            mapping(5482, "ESH3", "2023-01-03", "2023-01-05"),
+            mapping(5602, "ESM3", "2023-01-03", "2023-01-05"),
+        ]);
+        assert_eq!(symbol_map.symbol(5482, date("2023-01-03")), Some("ESH3"));
+        assert_eq!(
+            symbol_map.symbol(5602, date("2023-01-04T23:59:59.999999999Z")),
+            Some("ESM3")
+        );
+        assert_eq!(symbol_map.symbol(5482, date("2023-01-05")), None);
+        assert_eq!(symbol_map.symbol(5482, date("2023-01-02")), None);
+        assert_eq!(symbol_map.symbol(1, date("2023-01-03")), None);
+        assert_eq!(
+            symbol_map.product_id("ESM3", date("2023-01-04")),
+            Some(5602)
+        );
+        assert_eq!(symbol_map.product_id("ESZ3", date("2023-01-04")), None);
+    }
+
+    #[test]
+    fn test_adjacent_intervals() {
+        let symbol_map = symbol_map(&[
+            mapping(5482, "ESH3", "2023-01-04", "2023-01-05"),
+            mapping(5482, "ESH3", "2023-01-03", "2023-01-04"),
+            mapping(5482, "ESH3", "2023-01-05", "2023-01-06"),
+        ]);
+        assert_eq!(symbol_map.symbols[&5482].len(), 1);
+        assert_eq!(symbol_map.product_ids["ESH3"].len(), 1);
+        for day in ["2023-01-03", "2023-01-04", "2023-01-05"] {
+            assert_eq!(symbol_map.symbol(5482, date(day)), Some("ESH3"));
+        }
+    }
+
+    #[test]
+    fn test_product_id_reuse() {
+        let symbol_map = symbol_map(&[
+            mapping(10, "ESH3", "2023-01-03", "2023-01-05"),
+            mapping(11, "NQH3", "2023-01-03", "2023-01-04"),
+            // Product ID 10 is reassigned on the second day
+            mapping(10, "NQH3", "2023-01-04", "2023-01-06"),
+            mapping(12, "ESH3", "2023-01-04", "2023-01-06"),
+        ]);
+        assert_eq!(symbol_map.symbol(10, date("2023-01-03")), Some("ESH3"));
+        assert_eq!(symbol_map.symbol(10, date("2023-01-04")), Some("NQH3"));
+        assert_eq!(symbol_map.symbol(11, date("2023-01-04")), None);
+        assert_eq!(symbol_map.product_id("ESH3", date("2023-01-03")), Some(10));
+        assert_eq!(symbol_map.product_id("ESH3", date("2023-01-04")), Some(12));
+        assert_eq!(symbol_map.product_id("NQH3", date("2023-01-03")), Some(11));
+        assert_eq!(symbol_map.product_id("NQH3", date("2023-01-05")), Some(10));
+    }
+
+    #[test]
+    fn test_overlapping_intervals() {
+        let symbol_map = symbol_map(&[
+            mapping(10, "ESH3", "2023-01-02", "2023-01-07"),
+            mapping(10, "ESM3", "2023-01-04", "2023-01-05"),
+        ]);
+        assert_eq!(symbol_map.symbol(10, date("2023-01-03")), Some("ESH3"));
+        assert_eq!(symbol_map.symbol(10, date("2023-01-04")), Some("ESM3"));
+        assert_eq!(symbol_map.symbol(10, date("2023-01-06")), Some("ESH3"));
+        assert_eq!(symbol_map.product_id("ESH3", date("2023-01-04")), None);
+        assert_eq!(symbol_map.product_id("ESH3", date("2023-01-05")), Some(10));
+        assert_eq!(symbol_map.product_id("ESM3", date("2023-01-04")), Some(10));
+    }
+
+    #[test]
+    fn test_invalid_interval() {
+        let mut symbol_map = SymbolMap::new();
+        assert!(matches!(
+            symbol_map.insert(&mapping(10, "ESH3", "2023-01-05", "2023-01-04")),
+            Err(Error::TypeConversion(_))
+        ));
+        symbol_map
+            .insert(&mapping(10, "ESH3", "2023-01-05", "2023-01-05"))
+            .unwrap();
+        assert!(symbol_map.is_empty());
+    }
+}
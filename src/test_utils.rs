@@ -0,0 +1,71 @@
+//! Helpers shared between the unit tests of different modules.
+use std::{io, os::raw::c_char};
+
+use crate::{
+    enums::{Action, Side},
+    record::{MboMsg, OhlcvMsg, RecordHeader},
+};
+
+pub(crate) const OHLCV_MSG: OhlcvMsg = OhlcvMsg {
+    hd: RecordHeader::new::<OhlcvMsg>(1, 5482, 1609160400000000000),
+    open: 372025000000000,
+    high: 372050000000000,
+    low: 372025000000000,
+    close: 372050000000000,
+    volume: 57,
+};
+
+pub(crate) const MBO_MSG: MboMsg = MboMsg {
+    hd: RecordHeader::new::<MboMsg>(1, 5482, 1658441851000000000),
+    order_id: 647784973705,
+    price: 3722750000000,
+    size: 1,
+    flags: 128,
+    channel_id: 0,
+    action: 'C' as c_char,
+    side: 'A' as c_char,
+    ts_recv: 1658441891000000000,
+    ts_in_delta: 22993,
+    sequence: 1170352,
+};
+
+/// Returns an MBO record for product 5482 from publisher 1.
+pub(crate) fn mbo(action: Action, side: Side, order_id: u64, price: i64, size: u32) -> MboMsg {
+    MboMsg {
+        hd: RecordHeader::new::<MboMsg>(1, 5482, 1658441851000000000),
+        order_id,
+        price,
+        size,
+        flags: 0,
+        channel_id: 0,
+        action: c_char::from(action),
+        side: c_char::from(side),
+        ts_recv: 1658441891000000000,
+        ts_in_delta: 0,
+        sequence: 0,
+    }
+}
+
+/// A reader that returns at most one byte per read to exercise partial reads.
+pub(crate) struct TrickleReader<'a>(pub &'a [u8]);
+
+impl io::Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+impl io::BufRead for TrickleReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&self.0[..self.0.len().min(1)])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0 = &self.0[amt..];
+    }
+}
@@ -0,0 +1,636 @@
+//! Nanosecond-resolution timestamp and duration types for the time fields of records.
+//!
+//! Conversions to and from the types of the [`time`](https://docs.rs/time) and
+//! [`chrono`](https://docs.rs/chrono) crates require the `time` and `chrono` features
+//! respectively.
+use std::{fmt, str::FromStr};
+
+use crate::{Error, Result};
+
+/// The raw value of an undefined or missing timestamp.
+pub const UNDEF_TIMESTAMP: u64 = u64::MAX;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const SECS_PER_DAY: u64 = 86_400;
+/// The string representation of [`Timestamp::UNDEF`].
+const UNDEF_STR: &str = "UNDEF_TIMESTAMP";
+
+/// A point in time expressed as the number of nanoseconds since the UNIX epoch. A raw
+/// value of [`UNDEF_TIMESTAMP`] denotes an undefined timestamp.
+///
+/// Timestamps are formatted and parsed in the ISO 8601 format
+/// `YYYY-MM-DDTHH:MM:SS.fffffffffZ`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Timestamp(
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::record::serialize_large_u64",
+            deserialize_with = "crate::record::deserialize_large_u64"
+        )
+    )]
+    u64,
+);
+
+/// A signed span of time in nanoseconds, such as the difference between two
+/// [`Timestamp`]s.
+///
+/// Durations are formatted and parsed in the ISO 8601 format `PTn.fffffffffS`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Duration(i64);
+
+impl Timestamp {
+    /// The undefined timestamp.
+    pub const UNDEF: Self = Self(UNDEF_TIMESTAMP);
+    /// The UNIX epoch, 1970-01-01T00:00:00Z.
+    pub const UNIX_EPOCH: Self = Self(0);
+
+    /// Creates a new timestamp from the number of nanoseconds since the UNIX epoch.
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    /// Returns the number of nanoseconds since the UNIX epoch.
+    pub const fn nanos(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the timestamp is [`Timestamp::UNDEF`].
+    pub const fn is_undefined(self) -> bool {
+        self.0 == UNDEF_TIMESTAMP
+    }
+
+    /// Adds a duration to the timestamp, returning `None` if the timestamp is undefined
+    /// or the result is out of range.
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        self.defined()?
+            .0
+            .checked_add_signed(duration.0)
+            .map(Self)
+            .and_then(Self::defined)
+    }
+
+    /// Subtracts a duration from the timestamp, returning `None` if the timestamp is
+    /// undefined or the result is out of range.
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        self.checked_add(Duration(duration.0.checked_neg()?))
+    }
+
+    /// Returns the duration from `earlier` to the timestamp, returning `None` if either
+    /// is undefined or the difference doesn't fit in a [`Duration`].
+    pub fn checked_duration_since(self, earlier: Self) -> Option<Duration> {
+        let diff = self.defined()?.0 as i128 - earlier.defined()?.0 as i128;
+        i64::try_from(diff).ok().map(Duration)
+    }
+
+    fn defined(self) -> Option<Self> {
+        if self.is_undefined() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+impl Duration {
+    /// A duration of zero.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a new duration from a number of nanoseconds.
+    pub const fn from_nanos(nanos: i64) -> Self {
+        Self(nanos)
+    }
+
+    /// Returns the number of nanoseconds in the duration.
+    pub const fn nanos(self) -> i64 {
+        self.0
+    }
+}
+
+impl From<u64> for Timestamp {
+    fn from(nanos: u64) -> Self {
+        Self(nanos)
+    }
+}
+
+impl From<Timestamp> for u64 {
+    fn from(ts: Timestamp) -> Self {
+        ts.0
+    }
+}
+
+impl From<i64> for Duration {
+    fn from(nanos: i64) -> Self {
+        Self(nanos)
+    }
+}
+
+impl From<Duration> for i64 {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<Self> {
+        u64::try_from(duration.0)
+            .map(Self::from_nanos)
+            .map_err(|_| Error::TypeConversion("Negative duration"))
+    }
+}
+
+impl TryFrom<std::time::Duration> for Duration {
+    type Error = Error;
+
+    fn try_from(duration: std::time::Duration) -> Result<Self> {
+        i64::try_from(duration.as_nanos())
+            .map(Self)
+            .map_err(|_| Error::TypeConversion("Duration out of range"))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_undefined() {
+            return f.pad(UNDEF_STR);
+        }
+        let secs = self.0 / NANOS_PER_SEC;
+        let (year, month, day) = civil_from_days((secs / SECS_PER_DAY) as i64);
+        let secs_of_day = secs % SECS_PER_DAY;
+        f.pad(&format!(
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:09}Z",
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60,
+            self.0 % NANOS_PER_SEC
+        ))
+    }
+}
+
+/// Parses an ISO 8601 date, e.g. `2022-07-21`, or date and time with an optional
+/// fraction of a second and UTC offset, e.g. `2022-07-21T22:17:31.5Z` or
+/// `2022-07-21 18:17:31-04:00`. Times without an offset are treated as UTC. The string
+/// `"UNDEF_TIMESTAMP"` is parsed as [`Timestamp::UNDEF`].
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        const INVALID: Error = Error::TypeConversion("Invalid ISO 8601 timestamp");
+
+        if s == UNDEF_STR {
+            return Ok(Self::UNDEF);
+        }
+        let mut parser = Parser(s.as_bytes());
+        let year = parser.digits(4).ok_or(INVALID)?;
+        parser.expect(b'-').ok_or(INVALID)?;
+        let month = parser.digits(2).ok_or(INVALID)?;
+        parser.expect(b'-').ok_or(INVALID)?;
+        let day = parser.digits(2).ok_or(INVALID)?;
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(INVALID);
+        }
+        let mut secs_of_day = 0;
+        let mut nanos = 0;
+        let mut offset_secs = 0;
+        if !parser.is_empty() {
+            parser
+                .expect(b'T')
+                .or_else(|| parser.expect(b' '))
+                .ok_or(INVALID)?;
+            let hour = parser.digits(2).ok_or(INVALID)?;
+            parser.expect(b':').ok_or(INVALID)?;
+            let minute = parser.digits(2).ok_or(INVALID)?;
+            parser.expect(b':').ok_or(INVALID)?;
+            let second = parser.digits(2).ok_or(INVALID)?;
+            if hour > 23 || minute > 59 || second > 59 {
+                return Err(INVALID);
+            }
+            secs_of_day = hour * 3600 + minute * 60 + second;
+            if parser.expect(b'.').is_some() {
+                nanos = parser.fraction().ok_or(INVALID)?;
+            }
+            offset_secs = parser.utc_offset().ok_or(INVALID)?;
+            if !parser.is_empty() {
+                return Err(INVALID);
+            }
+        }
+        let days = days_from_civil(year as i64, month, day);
+        let secs = days * SECS_PER_DAY as i64 + secs_of_day as i64 - offset_secs;
+        u64::try_from(secs as i128 * NANOS_PER_SEC as i128 + nanos as i128)
+            .ok()
+            .map(Self)
+            .and_then(Self::defined)
+            .ok_or(Error::TypeConversion("Timestamp out of range"))
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        f.pad(&format!(
+            "{sign}PT{}.{:09}S",
+            abs / NANOS_PER_SEC,
+            abs % NANOS_PER_SEC
+        ))
+    }
+}
+
+/// Parses an ISO 8601 duration containing only hours, minutes, and seconds with an
+/// optional fraction of a second and leading sign, e.g. `PT1H30M`, `PT0.000022993S`,
+/// or `-PT5S`.
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        const INVALID: Error = Error::TypeConversion("Invalid ISO 8601 duration");
+
+        let (is_negative, unsigned) = match s.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let mut parser = Parser(unsigned.strip_prefix("PT").ok_or(INVALID)?.as_bytes());
+        if parser.is_empty() {
+            return Err(INVALID);
+        }
+        let mut nanos: i128 = 0;
+        // Designators must appear in order and at most once
+        let mut designators = [(b'H', 3600), (b'M', 60), (b'S', 1)].into_iter();
+        while !parser.is_empty() {
+            let value = parser.number().ok_or(INVALID)?;
+            let fraction = if parser.expect(b'.').is_some() {
+                Some(parser.fraction().ok_or(INVALID)?)
+            } else {
+                None
+            };
+            let designator = parser.next().ok_or(INVALID)?;
+            let (_, secs) = designators.find(|(d, _)| *d == designator).ok_or(INVALID)?;
+            if fraction.is_some() && designator != b'S' {
+                return Err(INVALID);
+            }
+            nanos += (value as i128 * secs as i128) * NANOS_PER_SEC as i128
+                + fraction.unwrap_or(0) as i128;
+        }
+        if is_negative {
+            nanos = -nanos;
+        }
+        i64::try_from(nanos)
+            .map(Self)
+            .map_err(|_| Error::TypeConversion("Duration out of range"))
+    }
+}
+
+/// A minimal parser over ASCII bytes.
+struct Parser<'a>(&'a [u8]);
+
+impl Parser<'_> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let (&first, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(first)
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        if self.0.first() == Some(&byte) {
+            self.0 = &self.0[1..];
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Parses exactly `count` decimal digits.
+    fn digits(&mut self, count: usize) -> Option<u32> {
+        let digits = self.0.get(..count)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.0 = &self.0[count..];
+        Some(digits.iter().fold(0, |acc, d| acc * 10 + (d - b'0') as u32))
+    }
+
+    /// Parses one or more decimal digits.
+    fn number(&mut self) -> Option<u64> {
+        let len = self.0.iter().take_while(|b| b.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+        let (digits, rest) = self.0.split_at(len);
+        self.0 = rest;
+        digits.iter().try_fold(0u64, |acc, d| {
+            acc.checked_mul(10)?.checked_add((d - b'0') as u64)
+        })
+    }
+
+    /// Parses between 1 and 9 digits of a fraction of a second as nanoseconds.
+    fn fraction(&mut self) -> Option<u64> {
+        let len = self.0.iter().take_while(|b| b.is_ascii_digit()).count();
+        if !(1..=9).contains(&len) {
+            return None;
+        }
+        let nanos = self.number()?;
+        Some(nanos * 10u64.pow(9 - len as u32))
+    }
+
+    /// Parses an optional `Z` or `±HH:MM` UTC offset, returning the offset in seconds.
+    fn utc_offset(&mut self) -> Option<i64> {
+        let sign = match self.0.first() {
+            None => return Some(0),
+            Some(b'Z') => {
+                self.0 = &self.0[1..];
+                return Some(0);
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            Some(_) => return None,
+        };
+        self.0 = &self.0[1..];
+        let hours = self.digits(2)?;
+        self.expect(b':')?;
+        let minutes = self.digits(2)?;
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+        Some(sign * (hours * 3600 + minutes * 60) as i64)
+    }
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the UNIX epoch of a date in the proleptic
+/// Gregorian calendar, using Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the year, month, and day of a number of days since the UNIX epoch in the
+/// proleptic Gregorian calendar, using Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Timestamp> for time::OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(ts: Timestamp) -> Result<Self> {
+        let nanos = ts
+            .defined()
+            .ok_or(Error::TypeConversion("Undefined timestamp"))?
+            .0;
+        Self::from_unix_timestamp_nanos(nanos as i128)
+            .map_err(|_| Error::TypeConversion("Timestamp out of range"))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for Timestamp {
+    type Error = Error;
+
+    fn try_from(dt: time::OffsetDateTime) -> Result<Self> {
+        u64::try_from(dt.unix_timestamp_nanos())
+            .ok()
+            .map(Self)
+            .and_then(Self::defined)
+            .ok_or(Error::TypeConversion("Timestamp out of range"))
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> Self {
+        Self::nanoseconds(duration.0)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(ts: Timestamp) -> Result<Self> {
+        let nanos = ts
+            .defined()
+            .ok_or(Error::TypeConversion("Undefined timestamp"))?
+            .0;
+        Self::from_timestamp(
+            (nanos / NANOS_PER_SEC) as i64,
+            (nanos % NANOS_PER_SEC) as u32,
+        )
+        .ok_or(Error::TypeConversion("Timestamp out of range"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for Timestamp {
+    type Error = Error;
+
+    fn try_from(dt: chrono::DateTime<chrono::Utc>) -> Result<Self> {
+        let nanos =
+            dt.timestamp() as i128 * NANOS_PER_SEC as i128 + dt.timestamp_subsec_nanos() as i128;
+        u64::try_from(nanos)
+            .ok()
+            .map(Self)
+            .and_then(Self::defined)
+            .ok_or(Error::TypeConversion("Timestamp out of range"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Duration> for chrono::Duration {
+    fn from(duration: Duration) -> Self {
+        Self::nanoseconds(duration.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Timestamp(1658441851000000000).to_string(),
+            "2022-07-21T22:17:31.000000000Z"
+        );
+        assert_eq!(Timestamp(0).to_string(), "1970-01-01T00:00:00.000000000Z");
+        assert_eq!(
+            Timestamp(951782400123456789).to_string(),
+            "2000-02-29T00:00:00.123456789Z"
+        );
+        assert_eq!(
+            Timestamp(UNDEF_TIMESTAMP - 1).to_string(),
+            "2554-07-21T23:34:33.709551614Z"
+        );
+        assert_eq!(Timestamp::UNDEF.to_string(), "UNDEF_TIMESTAMP");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "2022-07-21T22:17:31Z".parse::<Timestamp>().unwrap(),
+            Timestamp(1658441851000000000)
+        );
+        assert_eq!(
+            "2022-07-21 18:17:31.5-04:00".parse::<Timestamp>().unwrap(),
+            Timestamp(1658441851500000000)
+        );
+        assert_eq!(
+            "2022-07-21".parse::<Timestamp>().unwrap(),
+            Timestamp(1658361600000000000)
+        );
+        assert_eq!(
+            "UNDEF_TIMESTAMP".parse::<Timestamp>().unwrap(),
+            Timestamp::UNDEF
+        );
+        for invalid in [
+            "",
+            "2022-7-21",
+            "2022-02-29",
+            "2022-07-21T",
+            "2022-07-21T24:00:00Z",
+            "2022-07-21T22:17:31.Z",
+            "2022-07-21T22:17:31.1234567890Z",
+            "2022-07-21T22:17:31+0400",
+            "2022-07-21T22:17:31Zoo",
+            "1969-12-31T23:59:59Z",
+            "2600-01-01",
+        ] {
+            assert!(invalid.parse::<Timestamp>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_display_from_str_round_trip() {
+        for nanos in [0, 1, 1658441851000000001, 4107542400000000000, u64::MAX - 1] {
+            let ts = Timestamp(nanos);
+            assert_eq!(ts.to_string().parse::<Timestamp>().unwrap(), ts);
+        }
+        // Every day in a 400-year cycle
+        for days in (0..146_097).step_by(7) {
+            let ts = Timestamp(days * SECS_PER_DAY * NANOS_PER_SEC);
+            assert_eq!(ts.to_string().parse::<Timestamp>().unwrap(), ts);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let ts = Timestamp(1658441851000000000);
+        let delta = Duration(22993);
+        assert_eq!(ts.checked_add(delta), Some(Timestamp(1658441851000022993)));
+        assert_eq!(ts.checked_sub(delta), Some(Timestamp(1658441850999977007)));
+        assert_eq!(
+            Timestamp(1658441851000022993).checked_duration_since(ts),
+            Some(delta)
+        );
+        assert_eq!(
+            ts.checked_duration_since(Timestamp(1658441851000022993)),
+            Some(Duration(-22993))
+        );
+        assert_eq!(Timestamp(0).checked_sub(Duration(1)), None);
+        assert_eq!(Timestamp(u64::MAX - 1).checked_add(Duration(1)), None);
+        assert_eq!(Timestamp::UNDEF.checked_add(Duration::ZERO), None);
+        assert_eq!(ts.checked_duration_since(Timestamp::UNDEF), None);
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(Duration(22993).to_string(), "PT0.000022993S");
+        assert_eq!(Duration(-1_500_000_000).to_string(), "-PT1.500000000S");
+        assert_eq!(
+            "PT0.000022993S".parse::<Duration>().unwrap(),
+            Duration(22993)
+        );
+        assert_eq!(
+            "-PT1H30M1.5S".parse::<Duration>().unwrap(),
+            Duration(-5_401_500_000_000)
+        );
+        assert_eq!(
+            "PT2M".parse::<Duration>().unwrap(),
+            Duration(120_000_000_000)
+        );
+        for invalid in [
+            "", "PT", "P1D", "PT1.5M", "PT1S1M", "PT1M1M", "PT1", "PT.5S",
+        ] {
+            assert!(invalid.parse::<Duration>().is_err(), "{invalid}");
+        }
+        assert_eq!(
+            std::time::Duration::try_from(Duration(22993)).unwrap(),
+            std::time::Duration::from_nanos(22993)
+        );
+        assert!(std::time::Duration::try_from(Duration(-1)).is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_conversion() {
+        let ts = Timestamp(1658441851000022993);
+        let dt = time::OffsetDateTime::try_from(ts).unwrap();
+        assert_eq!(dt.unix_timestamp_nanos(), 1658441851000022993);
+        assert_eq!(Timestamp::try_from(dt).unwrap(), ts);
+        assert!(time::OffsetDateTime::try_from(Timestamp::UNDEF).is_err());
+        assert!(
+            Timestamp::try_from(time::OffsetDateTime::UNIX_EPOCH - time::Duration::SECOND).is_err()
+        );
+        assert_eq!(
+            time::Duration::from(Duration(-22993)),
+            time::Duration::nanoseconds(-22993)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_conversion() {
+        let ts = Timestamp(1658441851000022993);
+        let dt = chrono::DateTime::<chrono::Utc>::try_from(ts).unwrap();
+        assert_eq!(dt.timestamp_nanos_opt(), Some(1658441851000022993));
+        assert_eq!(Timestamp::try_from(dt).unwrap(), ts);
+        assert!(chrono::DateTime::<chrono::Utc>::try_from(Timestamp::UNDEF).is_err());
+        assert_eq!(
+            chrono::Duration::from(Duration(22993)),
+            chrono::Duration::nanoseconds(22993)
+        );
+    }
+}